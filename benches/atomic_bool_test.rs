@@ -1,27 +1,21 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use std::sync::atomic::{AtomicBool, Ordering};
+use whython_4::{Program, Vm};
 extern crate whython_4;
 
-static CTRLC: AtomicBool = AtomicBool::new(false);
-
 fn criterion_benchmark(c: &mut Criterion) {
-    ctrlc::set_handler(|| {
-        CTRLC.store(true, Ordering::Relaxed);
-    })
-    .expect("Error setting Ctrl-C handler");
-
-    let mut memory = match whython_4::processing::processor::MemoryManagers::load_from_compiled(
-        "Compiled - 64.cwhy".to_string(),
-    ) {
+    let program = match Program::load("Compiled.cwhy") {
         Err(e) => {
             println!("Loading precompiled file failed - {}", e);
             return;
         }
         Ok(value) => value,
     };
-    c.bench_function("atomic", |b| {
-        b.iter(|| whython_4::execution::execute(&mut memory, &CTRLC))
-    });
+
+    let mut vm = Vm::new().with_stdout(std::io::sink());
+    let cancellation_token = vm.get_cancellation_token();
+    ctrlc::set_handler(move || cancellation_token.cancel()).expect("Error setting Ctrl-C handler");
+
+    c.bench_function("atomic", |b| b.iter(|| vm.run(&program)));
 }
 
 criterion_group!(benches, criterion_benchmark);
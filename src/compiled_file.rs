@@ -0,0 +1,199 @@
+use crate::errors::LoadError;
+use crate::util::POINTER_SIZE;
+
+/// Identifies a compiled Whython file
+pub const MAGIC: [u8; 4] = *b"CWHY";
+
+/// Version of the compiled file format. Increased whenever the layout or bytecode changes
+pub const FORMAT_VERSION: u16 = 2;
+
+/// Endianness flag for little-endian encoded data - the only encoding the VM uses
+pub const LITTLE_ENDIAN: u8 = 0;
+
+/// Size of the fixed header before the section table
+const HEADER_SIZE: usize = 16;
+
+/// Size of each entry in the section table
+const SECTION_ENTRY_SIZE: usize = 20;
+
+/// Kinds of section stored in a compiled file
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SectionKind {
+    Variables,
+    Program,
+    DebugInfo,
+}
+
+impl SectionKind {
+    fn to_id(self) -> u32 {
+        match self {
+            SectionKind::Variables => 0,
+            SectionKind::Program => 1,
+            SectionKind::DebugInfo => 2,
+        }
+    }
+
+    /// Returns `None` for sections added by later format versions
+    fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(SectionKind::Variables),
+            1 => Some(SectionKind::Program),
+            2 => Some(SectionKind::DebugInfo),
+            _ => None,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            SectionKind::Variables => "variables",
+            SectionKind::Program => "program",
+            SectionKind::DebugInfo => "debug info",
+        }
+    }
+}
+
+/// The contents of a `.cwhy` file
+///
+/// # File format
+/// All header values are little-endian
+/// * Magic number `CWHY` (4 bytes)
+/// * Format version (`u16`)
+/// * Size of `ptr` values and instruction operands in bytes (`u8`)
+/// * Endianness of the sections (`u8`) - 0 for little-endian
+/// * Section count (`u32`)
+/// * CRC-32 of everything after the header (`u32`)
+/// * Section table - kind (`u32`), offset from the start of the file (`u64`) and length (`u64`)
+///   of each section
+/// * Section data
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CompiledFile {
+    pub variable_memory: Vec<u8>,
+    pub program_memory: Vec<u8>,
+    pub debug_info: Option<Vec<u8>>,
+}
+
+impl CompiledFile {
+    /// Encodes the file with a header for this platform
+    pub fn encode(&self) -> Vec<u8> {
+        let mut sections = vec![
+            (SectionKind::Variables, &self.variable_memory),
+            (SectionKind::Program, &self.program_memory),
+        ];
+        if let Some(debug_info) = &self.debug_info {
+            sections.push((SectionKind::DebugInfo, debug_info));
+        }
+
+        let mut body = Vec::new();
+        let mut offset = HEADER_SIZE + sections.len() * SECTION_ENTRY_SIZE;
+        for (kind, data) in &sections {
+            body.extend(kind.to_id().to_le_bytes());
+            body.extend((offset as u64).to_le_bytes());
+            body.extend((data.len() as u64).to_le_bytes());
+            offset += data.len();
+        }
+        for (_, data) in &sections {
+            body.extend(data.iter());
+        }
+
+        let mut file = Vec::with_capacity(HEADER_SIZE + body.len());
+        file.extend(MAGIC);
+        file.extend(FORMAT_VERSION.to_le_bytes());
+        file.push(POINTER_SIZE as u8);
+        file.push(LITTLE_ENDIAN);
+        file.extend((sections.len() as u32).to_le_bytes());
+        file.extend(crc32(&body).to_le_bytes());
+        file.extend(body);
+        file
+    }
+
+    /// Decodes a file, rejecting it if it is corrupt or was compiled for an incompatible platform
+    pub fn decode(data: &[u8]) -> Result<Self, LoadError> {
+        if data.len() < MAGIC.len() || data[..MAGIC.len()] != MAGIC {
+            return Err(LoadError::NotCompiledFile);
+        }
+        if data.len() < HEADER_SIZE {
+            return Err(LoadError::Truncated);
+        }
+
+        let version = u16::from_le_bytes([data[4], data[5]]);
+        if version != FORMAT_VERSION {
+            return Err(LoadError::UnsupportedVersion {
+                version,
+                supported: FORMAT_VERSION,
+            });
+        }
+
+        let pointer_width = data[6] as usize;
+        if pointer_width != POINTER_SIZE {
+            return Err(LoadError::PointerWidthMismatch {
+                found: pointer_width,
+                expected: POINTER_SIZE,
+            });
+        }
+
+        if data[7] != LITTLE_ENDIAN {
+            return Err(LoadError::UnsupportedEndianness);
+        }
+
+        let section_count = read_u32(data, 8) as usize;
+        let mut file = CompiledFile::default();
+        let mut variable_memory = None;
+        let mut program_memory = None;
+        for i in 0..section_count {
+            let entry = HEADER_SIZE + i * SECTION_ENTRY_SIZE;
+            if entry + SECTION_ENTRY_SIZE > data.len() {
+                return Err(LoadError::Truncated);
+            }
+
+            let offset = usize::try_from(read_u64(data, entry + 4));
+            let length = usize::try_from(read_u64(data, entry + 12));
+            let section = match (offset, length) {
+                (Ok(offset), Ok(length)) => offset
+                    .checked_add(length)
+                    .and_then(|end| data.get(offset..end)),
+                _ => None,
+            }
+            .ok_or(LoadError::Truncated)?
+            .to_vec();
+
+            match SectionKind::from_id(read_u32(data, entry)) {
+                Some(SectionKind::Variables) => variable_memory = Some(section),
+                Some(SectionKind::Program) => program_memory = Some(section),
+                Some(SectionKind::DebugInfo) => file.debug_info = Some(section),
+                None => {}
+            }
+        }
+
+        // Checked after the section table so truncated files are reported as such
+        if crc32(&data[HEADER_SIZE..]) != read_u32(data, 12) {
+            return Err(LoadError::ChecksumMismatch);
+        }
+
+        file.variable_memory =
+            variable_memory.ok_or(LoadError::MissingSection(SectionKind::Variables))?;
+        file.program_memory =
+            program_memory.ok_or(LoadError::MissingSection(SectionKind::Program))?;
+        Ok(file)
+    }
+}
+
+fn read_u32(data: &[u8], position: usize) -> u32 {
+    u32::from_le_bytes(data[position..position + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], position: usize) -> u64 {
+    u64::from_le_bytes(data[position..position + 8].try_into().unwrap())
+}
+
+/// CRC-32 (IEEE) checksum
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
@@ -0,0 +1,58 @@
+use crate::errors::Diagnostic;
+use crate::processing::preprocessor::convert_to_symbols;
+use crate::processing::processor::process_symbols;
+use crate::program::Program;
+
+/// Compiles Whython source code into `Program`s
+///
+/// # Example
+/// ```
+/// use whython_4::Compiler;
+///
+/// let program = Compiler::new().compile("example.why", "int a = 1\nprint a\n");
+/// assert!(program.is_ok());
+/// ```
+pub struct Compiler {
+    debug_info: bool,
+}
+
+impl Default for Compiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Compiler {
+    /// Creates a compiler that includes debug info in the programs it compiles
+    pub fn new() -> Self {
+        Self { debug_info: true }
+    }
+
+    /// Sets whether compiled programs include debug info mapping them back to their source
+    pub fn with_debug_info(mut self, debug_info: bool) -> Self {
+        self.debug_info = debug_info;
+        self
+    }
+
+    /// Compiles `source`. `file_name` is used in diagnostics and debug info. Fails with every
+    /// error found, each of which can be rendered against `source`
+    pub fn compile(&self, file_name: &str, source: &str) -> Result<Program, Vec<Diagnostic>> {
+        let with_file = |diagnostics: Vec<Diagnostic>| -> Vec<Diagnostic> {
+            diagnostics
+                .into_iter()
+                .map(|d| d.with_file(file_name))
+                .collect()
+        };
+
+        let symbols = convert_to_symbols(source).map_err(with_file)?;
+        let (memory_managers, mut debug_info) = process_symbols(symbols).map_err(with_file)?;
+
+        let debug_info = if self.debug_info {
+            debug_info.set_source(file_name, source);
+            Some(debug_info)
+        } else {
+            None
+        };
+        Ok(Program::from_memory_managers(memory_managers, debug_info))
+    }
+}
@@ -0,0 +1,172 @@
+use crate::errors::LoadError;
+use crate::util::{encode_usize, get_usize, POINTER_SIZE};
+
+/// The first instruction compiled from a source line
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LineEntry {
+    /// Program address of the first instruction
+    pub address: usize,
+    /// Line index in the source file
+    pub line: usize,
+    pub text: String,
+}
+
+/// A variable declared in the source
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct VariableEntry {
+    pub name: String,
+    pub type_name: String,
+    /// Variable address - may be relative to the current stack frame
+    pub address: usize,
+    pub size: usize,
+    /// Program addresses the variable is in scope for. `scope_end` is exclusive
+    pub scope_start: usize,
+    pub scope_end: usize,
+}
+
+impl VariableEntry {
+    /// Returns `true` if the variable is in scope at the program address
+    pub fn in_scope(&self, address: usize) -> bool {
+        self.scope_start <= address && address < self.scope_end
+    }
+}
+
+/// Maps compiled program addresses back to the source they came from
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct DebugInfo {
+    pub file: String,
+    /// Sorted by address
+    pub lines: Vec<LineEntry>,
+    pub variables: Vec<VariableEntry>,
+}
+
+impl DebugInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the start of a source line's instructions. A line at the same address as the
+    /// previous one replaces it as the previous line produced no instructions
+    pub fn add_line(&mut self, address: usize, line: usize) {
+        if self.lines.last().map(|l| l.address) == Some(address) {
+            self.lines.pop();
+        }
+        self.lines.push(LineEntry {
+            address,
+            line,
+            text: String::new(),
+        });
+    }
+
+    /// Sets the source file and fills in the text of each line from its source
+    pub fn set_source(&mut self, file: &str, source: &str) {
+        self.file = file.to_string();
+        let source_lines: Vec<&str> = source.lines().collect();
+        for line in self.lines.iter_mut() {
+            if let Some(text) = source_lines.get(line.line) {
+                line.text = text.trim().to_string();
+            }
+        }
+    }
+
+    /// Gets the source line that the instruction at the program address was compiled from
+    pub fn get_line(&self, address: usize) -> Option<&LineEntry> {
+        match self.lines.partition_point(|l| l.address <= address) {
+            0 => None,
+            i => Some(&self.lines[i - 1]),
+        }
+    }
+
+    /// Returns the entry if a source line starts at exactly this program address
+    pub fn get_line_starting_at(&self, address: usize) -> Option<&LineEntry> {
+        self.get_line(address).filter(|l| l.address == address)
+    }
+
+    /// Gets the variables in scope at the program address
+    pub fn get_variables_in_scope(&self, address: usize) -> impl Iterator<Item = &VariableEntry> {
+        self.variables.iter().filter(move |v| v.in_scope(address))
+    }
+
+    /// Encodes the debug info for the debug info section of a compiled file
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        encode_string(&mut data, &self.file);
+
+        data.extend(encode_usize(self.lines.len()));
+        for line in &self.lines {
+            data.extend(encode_usize(line.address));
+            data.extend(encode_usize(line.line));
+            encode_string(&mut data, &line.text);
+        }
+
+        data.extend(encode_usize(self.variables.len()));
+        for variable in &self.variables {
+            encode_string(&mut data, &variable.name);
+            encode_string(&mut data, &variable.type_name);
+            data.extend(encode_usize(variable.address));
+            data.extend(encode_usize(variable.size));
+            data.extend(encode_usize(variable.scope_start));
+            data.extend(encode_usize(variable.scope_end));
+        }
+        data
+    }
+
+    /// Decodes debug info encoded by `encode`
+    pub fn decode(data: &[u8]) -> Result<Self, LoadError> {
+        let mut pointer = 0;
+        let file = decode_string(&mut pointer, data)?;
+
+        let line_count = decode_usize(&mut pointer, data)?;
+        let mut lines = Vec::new();
+        for _ in 0..line_count {
+            lines.push(LineEntry {
+                address: decode_usize(&mut pointer, data)?,
+                line: decode_usize(&mut pointer, data)?,
+                text: decode_string(&mut pointer, data)?,
+            });
+        }
+
+        let variable_count = decode_usize(&mut pointer, data)?;
+        let mut variables = Vec::new();
+        for _ in 0..variable_count {
+            variables.push(VariableEntry {
+                name: decode_string(&mut pointer, data)?,
+                type_name: decode_string(&mut pointer, data)?,
+                address: decode_usize(&mut pointer, data)?,
+                size: decode_usize(&mut pointer, data)?,
+                scope_start: decode_usize(&mut pointer, data)?,
+                scope_end: decode_usize(&mut pointer, data)?,
+            });
+        }
+
+        Ok(Self {
+            file,
+            lines,
+            variables,
+        })
+    }
+}
+
+fn encode_string(data: &mut Vec<u8>, string: &str) {
+    data.extend(encode_usize(string.len()));
+    data.extend(string.as_bytes());
+}
+
+fn decode_usize(pointer: &mut usize, data: &[u8]) -> Result<usize, LoadError> {
+    if data.len() - *pointer < POINTER_SIZE {
+        return Err(LoadError::InvalidDebugInfo);
+    }
+    let value = get_usize(pointer, data);
+    *pointer += POINTER_SIZE;
+    Ok(value)
+}
+
+fn decode_string(pointer: &mut usize, data: &[u8]) -> Result<String, LoadError> {
+    let length = decode_usize(pointer, data)?;
+    if data.len() - *pointer < length {
+        return Err(LoadError::InvalidDebugInfo);
+    }
+    let string = String::from_utf8_lossy(&data[*pointer..*pointer + length]).to_string();
+    *pointer += length;
+    Ok(string)
+}
@@ -0,0 +1,470 @@
+use crate::col_println;
+use crate::debug_info::DebugInfo;
+use crate::errors::{ExecutionError, RuntimeError};
+use crate::execution::execute_instruction;
+use crate::memory_manager::FRAME_RELATIVE_FLAG;
+use crate::processing::processor::MemoryManagers;
+use crate::processing::types::boolean::BOOLEAN_TRUE;
+use crate::program_io::{ProgramIo, StdIo};
+use crate::translator::describe_instruction;
+use crate::util::{get_usize, POINTER_SIZE};
+use std::io::{stdin, stdout, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const HELP: &str = "\
+Commands:
+  break <line>              Break before the first instruction of a source line
+  break @<address>          Break before the instruction at a program address
+  watch <variable>          Stop when a variable changes
+  watch @<address> <length> Stop when a range of variable memory changes
+  delete <id>               Remove a breakpoint or watchpoint
+  info                      List breakpoints and watchpoints
+  step                      Execute one instruction
+  continue                  Run until a breakpoint, watchpoint or the end of the program
+  print <variable>          Print a variable in scope
+  backtrace                 Print the active function calls
+  where                     Print the current instruction
+  quit                      Stop debugging";
+
+enum Stop {
+    Address(usize),
+    /// A range of variable memory and its value when last checked
+    Watch {
+        name: String,
+        /// Frame relative for variables in a stack frame
+        address: usize,
+        length: usize,
+        value: Option<Vec<u8>>,
+        /// Frame of a frame relative `address`
+        frame: Option<WatchedFrame>,
+    },
+}
+
+/// The stack frame a watched variable is in. Its memory is reused once the frame is popped
+#[derive(Clone, Copy)]
+struct WatchedFrame {
+    /// Stack depth while the frame is the current frame
+    depth: usize,
+    frame_pointer: usize,
+}
+
+/// Gets the absolute address of watched memory
+fn resolve_watch(address: usize, frame: Option<WatchedFrame>) -> usize {
+    match frame {
+        Some(frame) => frame.frame_pointer + (address & !FRAME_RELATIVE_FLAG),
+        None => address,
+    }
+}
+
+/// Steps through a compiled program, stopping at breakpoints and watchpoints
+struct Debugger<'a> {
+    memory_managers: &'a mut MemoryManagers,
+    debug_info: Option<&'a DebugInfo>,
+    pointer: usize,
+    io: StdIo,
+    /// Breakpoints and watchpoints with their ids
+    stops: Vec<(usize, Stop)>,
+    next_id: usize,
+}
+
+/// Executes the compiled program under an interactive debugger reading commands from stdin
+pub fn debug(
+    memory_managers: &mut MemoryManagers,
+    debug_info: Option<&DebugInfo>,
+    exit: &AtomicBool,
+) -> Result<(), ExecutionError> {
+    memory_managers.variable_memory.init_stack();
+    let mut debugger = Debugger {
+        memory_managers,
+        debug_info,
+        pointer: 0,
+        io: StdIo,
+        stops: Vec::new(),
+        next_id: 1,
+    };
+
+    col_println!((cyan, bold), "Debugging program - type 'help' for commands");
+    if debug_info.is_none() {
+        col_println!(
+            yellow,
+            "No debug info - source lines and variables are unavailable"
+        );
+    }
+    debugger.print_location();
+
+    let result = debugger.run(exit);
+    debugger.memory_managers.variable_memory.clear_stack();
+    result
+}
+
+impl<'a> Debugger<'a> {
+    fn is_finished(&self) -> bool {
+        self.pointer >= self.memory_managers.program_memory.memory.len()
+    }
+
+    /// Reads and runs commands until the program finishes or the user quits
+    fn run(&mut self, exit: &AtomicBool) -> Result<(), ExecutionError> {
+        loop {
+            print!("(debug) ");
+            stdout().flush().ok();
+            let mut line = String::new();
+            if stdin().read_line(&mut line).unwrap_or(0) == 0 {
+                return Ok(());
+            }
+
+            let arguments: Vec<&str> = line.split_whitespace().collect();
+            let (command, arguments) = match arguments.split_first() {
+                Some((command, arguments)) => (*command, arguments),
+                None => continue,
+            };
+
+            match command {
+                "help" | "h" => println!("{}", HELP),
+                "break" | "b" => self.add_breakpoint(arguments),
+                "watch" | "w" => self.add_watchpoint(arguments),
+                "delete" | "d" => self.delete(arguments),
+                "info" | "i" => self.print_stops(),
+                "print" | "p" => self.print_variable(arguments),
+                "backtrace" | "bt" => self.print_backtrace(),
+                "where" => self.print_location(),
+                "step" | "s" => {
+                    self.step()?;
+                    self.check_watchpoints();
+                    self.print_location();
+                }
+                "continue" | "c" => self.resume(exit)?,
+                "quit" | "q" => return Ok(()),
+                _ => col_println!(red, "Unknown command '{}' - type 'help'", command),
+            }
+
+            if self.is_finished() {
+                col_println!((green, bold), "Program finished");
+                return Ok(());
+            }
+        }
+    }
+
+    /// Executes one instruction
+    fn step(&mut self) -> Result<(), ExecutionError> {
+        if self.is_finished() {
+            return Ok(());
+        }
+        let address = self.pointer;
+        execute_instruction(&mut self.pointer, self.memory_managers, &mut self.io)
+            .and_then(|_| self.io.flush())
+            .map_err(|error| ExecutionError { error, address })
+    }
+
+    /// Executes instructions until a breakpoint, watchpoint or the end of the program
+    fn resume(&mut self, exit: &AtomicBool) -> Result<(), ExecutionError> {
+        loop {
+            self.step()?;
+            if self.is_finished() {
+                return Ok(());
+            }
+
+            if exit.swap(false, Ordering::Relaxed) {
+                col_println!(yellow, "{}", RuntimeError::Interrupted);
+                break;
+            }
+
+            let breakpoint = self.stops.iter().find_map(|(id, stop)| match stop {
+                Stop::Address(address) if *address == self.pointer => Some(*id),
+                _ => None,
+            });
+            if let Some(id) = breakpoint {
+                col_println!(yellow, "Breakpoint {}", id);
+                break;
+            }
+            if self.check_watchpoints() {
+                break;
+            }
+        }
+        self.print_location();
+        Ok(())
+    }
+
+    /// Prints any watched memory that has changed, removing watchpoints on variables whose frame
+    /// has been popped. Returns `true` if any has changed or been removed
+    fn check_watchpoints(&mut self) -> bool {
+        let variable_memory = &self.memory_managers.variable_memory;
+        let mut changed = false;
+        self.stops.retain(|(id, stop)| match stop {
+            Stop::Watch {
+                name,
+                frame: Some(frame),
+                ..
+            } if frame.depth > variable_memory.get_stack_depth() => {
+                col_println!(
+                    yellow,
+                    "Watchpoint {} - '{}' went out of scope and was deleted",
+                    id,
+                    name
+                );
+                changed = true;
+                false
+            }
+            _ => true,
+        });
+
+        for (id, stop) in self.stops.iter_mut() {
+            if let Stop::Watch {
+                name,
+                address,
+                length,
+                value,
+                frame,
+            } = stop
+            {
+                let new_value = variable_memory
+                    .read(resolve_watch(*address, *frame), *length)
+                    .ok()
+                    .map(|v| v.to_vec());
+                if new_value != *value {
+                    col_println!(
+                        yellow,
+                        "Watchpoint {} - '{}' changed from {:X?} to {:X?}",
+                        id,
+                        name,
+                        value.as_deref().unwrap_or(&[]),
+                        new_value.as_deref().unwrap_or(&[])
+                    );
+                    *value = new_value;
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    fn add_stop(&mut self, stop: Stop) {
+        let id = self.next_id;
+        self.next_id += 1;
+        match &stop {
+            Stop::Address(address) => println!("Breakpoint {} at [{:0>5}]", id, address),
+            Stop::Watch { name, .. } => println!("Watchpoint {} on '{}'", id, name),
+        }
+        self.stops.push((id, stop));
+    }
+
+    fn add_breakpoint(&mut self, arguments: &[&str]) {
+        let argument = match arguments.first() {
+            Some(argument) => *argument,
+            None => return col_println!(red, "Expected a line or @address"),
+        };
+
+        if let Some(address) = argument.strip_prefix('@') {
+            match address.parse() {
+                Ok(address) => self.add_stop(Stop::Address(address)),
+                Err(_) => col_println!(red, "Invalid address '{}'", address),
+            }
+            return;
+        }
+
+        let line: usize = match argument.parse() {
+            Ok(line) if line >= 1 => line,
+            _ => return col_println!(red, "Invalid line '{}'", argument),
+        };
+        let debug_info = match self.debug_info {
+            Some(debug_info) => debug_info,
+            None => return col_println!(red, "Line breakpoints need debug info"),
+        };
+
+        let addresses: Vec<usize> = debug_info
+            .lines
+            .iter()
+            .filter(|l| l.line == line - 1)
+            .map(|l| l.address)
+            .collect();
+        if addresses.is_empty() {
+            col_println!(red, "No instructions on line {}", line);
+        }
+        for address in addresses {
+            self.add_stop(Stop::Address(address));
+        }
+    }
+
+    fn add_watchpoint(&mut self, arguments: &[&str]) {
+        let (name, address, length) = match arguments {
+            [address, length] if address.starts_with('@') => {
+                match (address[1..].parse(), length.parse()) {
+                    (Ok(address), Ok(length)) => (format!("@{}", address), address, length),
+                    _ => return col_println!(red, "Invalid address or length"),
+                }
+            }
+            [name] => match self.find_variable(name) {
+                Some((address, length, _)) => (name.to_string(), address, length),
+                None => return col_println!(red, "No variable '{}' in scope", name),
+            },
+            _ => return col_println!(red, "Expected a variable or @address and length"),
+        };
+
+        let variable_memory = &self.memory_managers.variable_memory;
+        //? `@address` watchpoints are always absolute
+        let frame = (address & FRAME_RELATIVE_FLAG != 0).then(|| WatchedFrame {
+            depth: variable_memory.get_stack_depth(),
+            frame_pointer: variable_memory.resolve(FRAME_RELATIVE_FLAG),
+        });
+        let value = variable_memory
+            .read(resolve_watch(address, frame), length)
+            .ok()
+            .map(|v| v.to_vec());
+        self.add_stop(Stop::Watch {
+            name,
+            address,
+            length,
+            value,
+            frame,
+        });
+    }
+
+    fn delete(&mut self, arguments: &[&str]) {
+        let id: usize = match arguments.first().map(|a| a.parse()) {
+            Some(Ok(id)) => id,
+            _ => return col_println!(red, "Expected a breakpoint or watchpoint id"),
+        };
+        let count = self.stops.len();
+        self.stops.retain(|(i, _)| *i != id);
+        if self.stops.len() == count {
+            col_println!(red, "No breakpoint or watchpoint {}", id);
+        }
+    }
+
+    fn print_stops(&self) {
+        if self.stops.is_empty() {
+            println!("No breakpoints or watchpoints");
+        }
+        for (id, stop) in &self.stops {
+            match stop {
+                Stop::Address(address) => {
+                    print!("{:>3}: breakpoint at [{:0>5}]", id, address);
+                    match self.debug_info.and_then(|d| d.get_line(*address)) {
+                        Some(line) => println!(" line {}", line.line + 1),
+                        None => println!(),
+                    }
+                }
+                Stop::Watch {
+                    name,
+                    address,
+                    length,
+                    frame,
+                    ..
+                } => {
+                    let address = resolve_watch(*address, *frame);
+                    println!(
+                        "{:>3}: watchpoint on '{}' [{}..{}]",
+                        id,
+                        name,
+                        address,
+                        address + length
+                    )
+                }
+            }
+        }
+    }
+
+    /// Finds a variable in scope. Returns its address, which may be frame relative, size and type
+    /// name
+    fn find_variable(&self, name: &str) -> Option<(usize, usize, String)> {
+        //? Later declarations shadow earlier ones
+        let variable = self
+            .debug_info?
+            .get_variables_in_scope(self.pointer)
+            .filter(|v| v.name == name)
+            .last()?;
+        Some((variable.address, variable.size, variable.type_name.clone()))
+    }
+
+    fn print_variable(&self, arguments: &[&str]) {
+        let name = match arguments.first() {
+            Some(name) => *name,
+            None => return col_println!(red, "Expected a variable name"),
+        };
+        let (address, size, type_name) = match self.find_variable(name) {
+            Some(variable) => variable,
+            None => return col_println!(red, "No variable '{}' in scope", name),
+        };
+
+        let variable_memory = &self.memory_managers.variable_memory;
+        match variable_memory.read(variable_memory.resolve(address), size) {
+            Ok(data) => println!(
+                "{} {} = {}",
+                type_name,
+                name,
+                format_value(&type_name, data)
+            ),
+            Err(e) => col_println!(red, "{}", e),
+        }
+    }
+
+    fn print_backtrace(&self) {
+        self.print_frame(0, self.pointer);
+        let return_addresses = self.memory_managers.variable_memory.get_return_addresses();
+        for (i, return_address) in return_addresses.into_iter().enumerate() {
+            //? Show the call rather than where execution resumes
+            self.print_frame(i + 1, return_address.saturating_sub(1));
+        }
+    }
+
+    fn print_frame(&self, index: usize, address: usize) {
+        print!("#{} [{:0>5}]", index, address);
+        match self.debug_info.and_then(|d| d.get_line(address)) {
+            Some(line) => println!(" line {} | {}", line.line + 1, line.text),
+            None => println!(),
+        }
+    }
+
+    fn print_location(&self) {
+        if self.is_finished() {
+            return;
+        }
+        if let Some(line) = self.debug_info.and_then(|d| d.get_line(self.pointer)) {
+            col_println!(
+                cyan,
+                "{}:{} | {}",
+                self.debug_info.unwrap().file,
+                line.line + 1,
+                line.text
+            );
+        }
+        println!(
+            "[{:0>5}] {}",
+            self.pointer,
+            describe_instruction(&self.memory_managers.program_memory.memory, self.pointer)
+                .unwrap_or_else(|| "<invalid instruction>".to_string())
+        );
+    }
+}
+
+/// Formats a variable's value using the name of its type
+fn format_value(type_name: &str, data: &[u8]) -> String {
+    //? Arrays e.g. 'Integer[3]'
+    if let Some((element_type, length)) = type_name
+        .strip_suffix(']')
+        .and_then(|t| t.split_once('['))
+        .and_then(|(t, length)| Some((t, length.parse::<usize>().ok()?)))
+    {
+        if length != 0 && data.len().is_multiple_of(length) {
+            let elements: Vec<String> = data
+                .chunks(data.len() / length)
+                .map(|element| format_value(element_type, element))
+                .collect();
+            return format!("[{}]", elements.join(", "));
+        }
+    }
+
+    match type_name {
+        "Integer" if data.len() == 8 => i64::from_le_bytes(data.try_into().unwrap()).to_string(),
+        "Pointer" if data.len() == POINTER_SIZE => get_usize(&0, data).to_string(),
+        "Boolean" if data.len() == 1 => (data[0] == BOOLEAN_TRUE).to_string(),
+        "Character" if data.len() == 1 => format!("'{}'", data[0] as char),
+        "String" if data.len() >= POINTER_SIZE => {
+            let length = get_usize(&0, data).min(data.len() - POINTER_SIZE);
+            format!(
+                "\"{}\"",
+                String::from_utf8_lossy(&data[POINTER_SIZE..POINTER_SIZE + length])
+            )
+        }
+        _ => format!("{:X?}", data),
+    }
+}
@@ -1,9 +1,543 @@
+use crate::compiled_file::SectionKind;
 use crate::processing::symbols::Operator;
 use crate::processing::types::{Type, TypeSymbol};
+use colored::Colorize;
+use std::fmt;
+use std::time::Duration;
 
-/// Takes zero-indexed line
-pub fn create_line_error<T>(error: String, line: usize) -> Result<T, String> {
-    Err(format!("Line {}: {}", line + 1, error))
+/// An error found while compiling source code
+#[derive(Clone, PartialEq, Debug)]
+pub enum CompileError {
+    /// A symbol doesn't match any known symbol
+    UnknownSymbol(String),
+    /// Brackets, indexers or strings aren't opened or closed correctly
+    UnbalancedDelimiter(String),
+    /// Indentation isn't a multiple of 4 or is too deep
+    BadIndentation(String),
+    /// A line didn't match any known line pattern
+    UnknownLine,
+    /// A line or block is formatted incorrectly
+    Syntax(String),
+    /// A value of one type was used where another was expected
+    TypeMismatch {
+        expected: TypeSymbol,
+        found: TypeSymbol,
+    },
+    OperatorNotImplemented {
+        operator: Operator,
+        lhs: TypeSymbol,
+        rhs: Option<TypeSymbol>,
+    },
+    /// A type doesn't support an operation e.g. calling an `int`
+    UnsupportedOperation(String),
+    /// A literal can't be used in this context e.g. assigning 300 to a `char`
+    InvalidLiteral(String),
+    UnknownVariable(String),
+    UnknownStruct(String),
+    UnknownField {
+        owner: String,
+        field: String,
+    },
+    /// A variable belongs to an enclosing function's stack frame
+    InaccessibleVariable(String),
+    DuplicateVariable(String),
+    DuplicateField(String),
+    DuplicateStruct(String),
+    ArgumentCount {
+        expected: usize,
+        found: usize,
+    },
+    /// A function without a return type was used as a value
+    NoReturnValue,
+    /// An expression doesn't produce a value
+    NoValue,
+    /// 'break', 'continue', 'return', 'elif' or 'else' used where it isn't allowed
+    InvalidControlFlow(String),
+    /// An error narrowed to the part of the line that caused it e.g. an unknown name
+    Spanned {
+        error: Box<CompileError>,
+        span: Span,
+    },
+}
+
+impl CompileError {
+    /// Narrows the error to `span`. Errors that have already been narrowed keep their span
+    pub fn with_span(self, span: Span) -> Self {
+        match self {
+            CompileError::Spanned { .. } => self,
+            error => CompileError::Spanned {
+                error: Box::new(error),
+                span,
+            },
+        }
+    }
+
+    /// Gets the error code shown with a `Diagnostic`
+    pub fn code(&self) -> &'static str {
+        match self {
+            CompileError::Spanned { error, .. } => error.code(),
+            CompileError::UnknownSymbol(_) => "E0001",
+            CompileError::UnbalancedDelimiter(_) => "E0002",
+            CompileError::BadIndentation(_) => "E0003",
+            CompileError::UnknownLine => "E0100",
+            CompileError::Syntax(_) => "E0101",
+            CompileError::TypeMismatch { .. } => "E0200",
+            CompileError::OperatorNotImplemented { .. } => "E0201",
+            CompileError::UnsupportedOperation(_) => "E0202",
+            CompileError::InvalidLiteral(_) => "E0203",
+            CompileError::UnknownVariable(_) => "E0300",
+            CompileError::UnknownStruct(_) => "E0301",
+            CompileError::UnknownField { .. } => "E0302",
+            CompileError::DuplicateVariable(_) => "E0303",
+            CompileError::DuplicateField(_) => "E0304",
+            CompileError::DuplicateStruct(_) => "E0305",
+            CompileError::InaccessibleVariable(_) => "E0306",
+            CompileError::ArgumentCount { .. } => "E0400",
+            CompileError::NoReturnValue => "E0401",
+            CompileError::NoValue => "E0402",
+            CompileError::InvalidControlFlow(_) => "E0500",
+        }
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompileError::Spanned { error, .. } => write!(f, "{}", error),
+            CompileError::UnknownSymbol(symbol) => write!(f, "Symbol '{}' not found", symbol),
+            CompileError::UnbalancedDelimiter(message)
+            | CompileError::BadIndentation(message)
+            | CompileError::Syntax(message)
+            | CompileError::UnsupportedOperation(message)
+            | CompileError::InvalidLiteral(message)
+            | CompileError::InvalidControlFlow(message) => write!(f, "{}", message),
+            CompileError::UnknownLine => write!(f, "Line didn't match any known patterns"),
+            CompileError::TypeMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Mismatching types - expected {} but found {}",
+                    expected, found
+                )
+            }
+            CompileError::OperatorNotImplemented {
+                operator,
+                lhs,
+                rhs: Some(rhs),
+            } => write!(
+                f,
+                "'{}' operator not implemented for '{}' and '{}'",
+                operator, lhs, rhs
+            ),
+            CompileError::OperatorNotImplemented {
+                operator,
+                lhs,
+                rhs: None,
+            } => write!(f, "'{}' operator not implemented for '{}'", operator, lhs),
+            CompileError::UnknownVariable(name) => write!(f, "Variable '{}' not found", name),
+            CompileError::UnknownStruct(name) => write!(f, "Struct '{}' not found", name),
+            CompileError::UnknownField { owner, field } => {
+                write!(f, "{} has no field '{}'", owner, field)
+            }
+            CompileError::DuplicateVariable(name) => {
+                write!(f, "Variable with name '{}' already exists", name)
+            }
+            CompileError::DuplicateField(name) => {
+                write!(f, "Field with name '{}' already exists", name)
+            }
+            CompileError::DuplicateStruct(name) => {
+                write!(f, "Struct with name '{}' already exists", name)
+            }
+            CompileError::InaccessibleVariable(name) => write!(
+                f,
+                "Variable '{}' belongs to an enclosing function and can't be accessed",
+                name
+            ),
+            CompileError::ArgumentCount { expected, found } => write!(
+                f,
+                "Wrong number of arguments - expected {} but found {}",
+                expected, found
+            ),
+            CompileError::NoReturnValue => write!(f, "Function does not return a value"),
+            CompileError::NoValue => write!(f, "Section does not evaluate to a value"),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// An error loading a compiled file
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    /// The file doesn't start with the compiled file magic number
+    NotCompiledFile,
+    UnsupportedVersion {
+        version: u16,
+        supported: u16,
+    },
+    /// The file was compiled with a different size of `ptr`. Widths are in bytes
+    PointerWidthMismatch {
+        found: usize,
+        expected: usize,
+    },
+    UnsupportedEndianness,
+    ChecksumMismatch,
+    MissingSection(SectionKind),
+    InvalidDebugInfo,
+    /// The file is too short to contain the data its header describes
+    Truncated,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "{}", e),
+            LoadError::NotCompiledFile => write!(f, "File is not a compiled Whython file"),
+            LoadError::UnsupportedVersion { version, supported } => write!(
+                f,
+                "Compiled file format version {} is not supported - expected version {}",
+                version, supported
+            ),
+            LoadError::PointerWidthMismatch { found, expected } => write!(
+                f,
+                "File was compiled with {}-bit pointers but this VM reads {}-bit pointers",
+                found * 8,
+                expected * 8
+            ),
+            LoadError::UnsupportedEndianness => {
+                write!(f, "Compiled file uses an unsupported byte order")
+            }
+            LoadError::ChecksumMismatch => {
+                write!(f, "Compiled file is corrupt - checksum does not match")
+            }
+            LoadError::MissingSection(kind) => write!(
+                f,
+                "Compiled file is missing its {} section",
+                kind.get_name()
+            ),
+            LoadError::InvalidDebugInfo => write!(f, "Compiled file has invalid debug info"),
+            LoadError::Truncated => write!(f, "Compiled file is truncated"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LoadError {
+    fn from(e: std::io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+/// An error while executing a compiled program. `address` is the program address of the
+/// instruction that failed
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RuntimeError {
+    UnknownOpcode {
+        code: u16,
+        address: usize,
+    },
+    DivisionByZero {
+        address: usize,
+    },
+    ModuloByZero {
+        address: usize,
+    },
+    /// A string grew beyond its capacity
+    StringOverflow {
+        length: usize,
+        capacity: usize,
+        address: usize,
+    },
+    IndexOutOfBounds {
+        index: usize,
+        length: usize,
+        address: usize,
+    },
+    SliceOutOfBounds {
+        start: usize,
+        end: usize,
+        length: usize,
+        address: usize,
+    },
+    StackOverflow {
+        max_depth: usize,
+    },
+    /// An instruction accessed variable memory outside of its bounds. `address` is the variable
+    /// address accessed
+    InvalidMemoryAccess {
+        address: usize,
+        length: usize,
+    },
+    /// The program ended part way through an instruction. `address` is the program address of
+    /// the missing data
+    UnexpectedEndOfProgram {
+        address: usize,
+    },
+    /// Variable memory grew beyond its maximum size
+    OutOfMemory {
+        size: usize,
+        max_memory: usize,
+    },
+    /// Returned from a function when no function was called
+    StackUnderflow,
+    /// Stopped by Ctrl+C or by cancelling a `Vm`
+    Interrupted,
+    /// Executed more instructions than a `Vm` allows
+    InstructionLimitExceeded {
+        limit: u64,
+    },
+    /// Ran for longer than a `Vm` allows
+    TimeLimitExceeded {
+        limit: Duration,
+    },
+    /// Reading program input or writing program output failed
+    Io {
+        message: String,
+    },
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuntimeError::UnknownOpcode { code, address } => write!(
+                f,
+                "Unknown code! [{}] at program address [{}]",
+                code, address
+            ),
+            RuntimeError::DivisionByZero { address } => {
+                write!(f, "Division by zero at program address [{}]", address)
+            }
+            RuntimeError::ModuloByZero { address } => {
+                write!(f, "Modulo by zero at program address [{}]", address)
+            }
+            RuntimeError::StringOverflow {
+                length,
+                capacity,
+                address,
+            } => write!(
+                f,
+                "String of length {} exceeds maximum length of {} at program address [{}]",
+                length, capacity, address
+            ),
+            RuntimeError::IndexOutOfBounds {
+                index,
+                length,
+                address,
+            } => write!(
+                f,
+                "Index {} out of range for length {} at program address [{}]",
+                index, length, address
+            ),
+            RuntimeError::SliceOutOfBounds {
+                start,
+                end,
+                length,
+                address,
+            } => write!(
+                f,
+                "Slice [{}, {}] out of range for string of length {} at program address [{}]",
+                start, end, length, address
+            ),
+            RuntimeError::StackOverflow { max_depth } => write!(
+                f,
+                "Stack overflow - exceeded maximum stack depth of {}",
+                max_depth
+            ),
+            RuntimeError::InvalidMemoryAccess { address, length } => write!(
+                f,
+                "Invalid access of {} byte(s) at variable address [{}]",
+                length, address
+            ),
+            RuntimeError::UnexpectedEndOfProgram { address } => write!(
+                f,
+                "Program ended unexpectedly while reading instruction at program address [{}]",
+                address
+            ),
+            RuntimeError::OutOfMemory { size, max_memory } => write!(
+                f,
+                "Out of memory - {} bytes exceeds maximum memory of {} bytes",
+                size, max_memory
+            ),
+            RuntimeError::StackUnderflow => {
+                write!(f, "Returned from function when no function was called")
+            }
+            RuntimeError::Interrupted => write!(f, "Program interrupted"),
+            RuntimeError::InstructionLimitExceeded { limit } => {
+                write!(f, "Exceeded limit of {} executed instructions", limit)
+            }
+            RuntimeError::TimeLimitExceeded { limit } => {
+                write!(f, "Exceeded execution time limit of {:?}", limit)
+            }
+            RuntimeError::Io { message } => write!(f, "Program I/O failed - {}", message),
+        }
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+/// A `RuntimeError` with the program address of the instruction being executed when it occurred
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExecutionError {
+    pub error: RuntimeError,
+    pub address: usize,
+}
+
+impl fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for ExecutionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// A range of character columns in a line. `end` is exclusive
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates a `Span` covering both `self` and `other`
+    pub fn join(&self, other: &Span) -> Self {
+        Self::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in the source code, pointing to where it occurred
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub error: CompileError,
+    pub file: Option<String>,
+    /// Zero-indexed line
+    pub line: usize,
+    pub span: Span,
+}
+
+impl Diagnostic {
+    /// Creates an error. Takes zero-indexed line. Errors narrowed with `CompileError::with_span`
+    /// are reported at their own span instead of `span`
+    pub fn error(error: CompileError, line: usize, span: Span) -> Self {
+        let (error, span) = match error {
+            CompileError::Spanned { error, span } => (*error, span),
+            error => (error, span),
+        };
+        Self {
+            severity: Severity::Error,
+            error,
+            file: None,
+            line,
+            span,
+        }
+    }
+
+    /// Sets the file this `Diagnostic` occurred in
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    /// Renders this `Diagnostic` with the offending line of `source` underlined
+    ///
+    /// # Example
+    /// ```text
+    /// error[E0001]: Symbol '$' not found
+    ///  --> main.why:3:7
+    ///   |
+    /// 3 | print $
+    ///   |       ^
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let header = format!("{}[{}]", self.severity, self.error.code());
+        let header = match self.severity {
+            Severity::Error => header.red().bold(),
+            Severity::Warning => header.yellow().bold(),
+        };
+        let mut output = format!("{}: {}\n", header, self.error.to_string().bold());
+
+        let line_number = (self.line + 1).to_string();
+        let gutter = " ".repeat(line_number.len());
+        let location = match &self.file {
+            Some(file) => format!("{}:{}:{}", file, self.line + 1, self.span.start + 1),
+            None => format!("{}:{}", self.line + 1, self.span.start + 1),
+        };
+        output += &format!("{}{} {}\n", gutter, "-->".blue().bold(), location);
+
+        //? Source may not contain the line e.g. an error at the end of the file
+        let source_line = match source.lines().nth(self.line) {
+            Some(source_line) => source_line,
+            None => return output,
+        };
+
+        //? Keep tabs so the underline lines up with the source
+        let padding: String = source_line
+            .chars()
+            .take(self.span.start)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let underline = "^".repeat((self.span.end.saturating_sub(self.span.start)).max(1));
+
+        output += &format!("{} {}\n", gutter, "|".blue().bold());
+        output += &format!(
+            "{} {} {}\n",
+            line_number.blue().bold(),
+            "|".blue().bold(),
+            source_line
+        );
+        output += &format!(
+            "{} {} {}{}",
+            gutter,
+            "|".blue().bold(),
+            padding,
+            underline.red().bold()
+        );
+
+        output
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Line {}:{}: {}",
+            self.line + 1,
+            self.span.start + 1,
+            self.error
+        )
+    }
 }
 
 /// Creates an error explaining that the operator isn't implemented for the given type
@@ -18,17 +552,10 @@ pub fn create_op_not_impl_error<T>(
     operator: &Operator,
     lhs: TypeSymbol,
     rhs: Option<&Type>,
-) -> Result<T, String> {
-    match rhs {
-        Some(rhs) => Err(format!(
-            "'{}' operator not implemented for '{}' and '{}'",
-            operator,
-            lhs,
-            rhs.get_type()
-        )),
-        None => Err(format!(
-            "'{}' operator not implemented for '{}'",
-            operator, lhs
-        )),
-    }
+) -> Result<T, CompileError> {
+    Err(CompileError::OperatorNotImplemented {
+        operator: *operator,
+        lhs,
+        rhs: rhs.map(|rhs| rhs.get_type()),
+    })
 }
@@ -1,62 +1,87 @@
-use crate::col_println;
+use crate::errors::{ExecutionError, RuntimeError};
 use crate::processing::instructions::add_instruction_13::AddInstruction;
 use crate::processing::instructions::and_instruction_6::AndInstruction;
+use crate::processing::instructions::call_instruction_22::CallInstruction;
+use crate::processing::instructions::concat_instruction_25::ConcatInstruction;
 use crate::processing::instructions::copy_instruction_0::CopyInstruction;
+use crate::processing::instructions::divide_instruction_18::DivideInstruction;
 use crate::processing::instructions::dynamic_from_copy_instruction_10::DynamicFromCopyInstruction;
 use crate::processing::instructions::dynamic_to_copy_instruction_11::DynamicToCopyInstruction;
 use crate::processing::instructions::equal_instruction_7::EqualInstruction;
+use crate::processing::instructions::greater_equal_instruction_21::GreaterEqualInstruction;
+use crate::processing::instructions::greater_instruction_20::GreaterInstruction;
 use crate::processing::instructions::input_instruction_15::InputInstruction;
 use crate::processing::instructions::invert_instruction_1::InvertInstruction;
 use crate::processing::instructions::jump_if_instruction_12::JumpIfInstruction;
 use crate::processing::instructions::jump_if_not_instruction_2::JumpIfNotInstruction;
 use crate::processing::instructions::jump_instruction_3::JumpInstruction;
 use crate::processing::instructions::jump_variable_instruction_4::JumpVariableInstruction;
+use crate::processing::instructions::modulo_instruction_19::ModuloInstruction;
+use crate::processing::instructions::multiply_instruction_17::MultiplyInstruction;
 use crate::processing::instructions::not_equal_instruction_14::NotEqualInstruction;
 use crate::processing::instructions::or_instruction_8::OrInstruction;
 use crate::processing::instructions::print_chars_instruction_9::PrintCharsInstruction;
 use crate::processing::instructions::print_instruction_5::PrintInstruction;
+use crate::processing::instructions::print_string_instruction_28::PrintStringInstruction;
+use crate::processing::instructions::reserve_frame_instruction_24::ReserveFrameInstruction;
+use crate::processing::instructions::return_instruction_23::ReturnInstruction;
+use crate::processing::instructions::slice_instruction_27::SliceInstruction;
+use crate::processing::instructions::string_index_instruction_26::StringIndexInstruction;
+use crate::processing::instructions::subtract_instruction_16::SubtractInstruction;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
 use crate::processing::processor::MemoryManagers;
+use crate::program_io::{ProgramIo, StdIo};
+use crate::status_println;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// Observes instructions as they are executed e.g. to trace or profile a program
+pub trait ExecutionMonitor {
+    /// Called before the instruction at `address` is executed
+    fn before_instruction(&mut self, address: usize, memory_managers: &MemoryManagers);
+
+    /// Called after the instruction at `address` has executed, whether or not it succeeded
+    fn after_instruction(
+        &mut self,
+        address: usize,
+        memory_managers: &MemoryManagers,
+        result: &Result<(), RuntimeError>,
+    );
+}
+
+/// Limits on how much a program may execute before it is stopped
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ExecutionLimits {
+    pub max_instructions: Option<u64>,
+    pub max_time: Option<Duration>,
+}
 
 /// Executes the compiled program
-pub fn execute(memory_managers: &mut MemoryManagers, exit: &AtomicBool) -> Result<(), String> {
-    let mut pointer: usize = 0;
-    let program_length = memory_managers.program_memory.memory.len();
+pub fn execute(
+    memory_managers: &mut MemoryManagers,
+    exit: &AtomicBool,
+) -> Result<(), ExecutionError> {
+    execute_with_monitor(memory_managers, exit, None)
+}
 
-    println!("Executing program");
+/// Executes the compiled program, reporting each instruction to `monitor`
+pub fn execute_with_monitor(
+    memory_managers: &mut MemoryManagers,
+    exit: &AtomicBool,
+    monitor: Option<&mut dyn ExecutionMonitor>,
+) -> Result<(), ExecutionError> {
+    status_println!(normal, "Executing program");
     let start_time = Instant::now();
 
-    while pointer < program_length {
-        let code = &memory_managers.program_memory.memory[pointer..pointer + 2];
-        pointer += 2;
-
-        match u16::from_le_bytes(code.try_into().unwrap()) {
-            0 => CopyInstruction::execute(&mut pointer, memory_managers),
-            1 => InvertInstruction::execute(&mut pointer, memory_managers),
-            2 => JumpIfNotInstruction::execute(&mut pointer, memory_managers),
-            3 => JumpInstruction::execute(&mut pointer, memory_managers),
-            4 => JumpVariableInstruction::execute(&mut pointer, memory_managers),
-            5 => PrintInstruction::execute(&mut pointer, memory_managers),
-            6 => AndInstruction::execute(&mut pointer, memory_managers),
-            7 => EqualInstruction::execute(&mut pointer, memory_managers),
-            8 => OrInstruction::execute(&mut pointer, memory_managers),
-            9 => PrintCharsInstruction::execute(&mut pointer, memory_managers),
-            10 => DynamicFromCopyInstruction::execute(&mut pointer, memory_managers),
-            11 => DynamicToCopyInstruction::execute(&mut pointer, memory_managers),
-            12 => JumpIfInstruction::execute(&mut pointer, memory_managers),
-            13 => AddInstruction::execute(&mut pointer, memory_managers),
-            14 => NotEqualInstruction::execute(&mut pointer, memory_managers),
-            15 => InputInstruction::execute(&mut pointer, memory_managers),
-            code => return Err(format!("Unknown code! [{}]", code)),
-        };
-
-        if exit.load(Ordering::Relaxed) {
-            return Err("Program terminated by Ctrl+C".to_string());
-        }
-    }
+    execute_with(
+        memory_managers,
+        exit,
+        &mut StdIo,
+        ExecutionLimits::default(),
+        monitor,
+    )?;
 
-    col_println!(
+    status_println!(
         (green, bold),
         "\nExecution completed [{:?}]",
         start_time.elapsed()
@@ -64,3 +89,137 @@ pub fn execute(memory_managers: &mut MemoryManagers, exit: &AtomicBool) -> Resul
 
     Ok(())
 }
+
+/// Executes the compiled program without printing any progress. The program reads and writes
+/// through `io` and is stopped with an error if it exceeds `limits`
+pub fn execute_with(
+    memory_managers: &mut MemoryManagers,
+    exit: &AtomicBool,
+    io: &mut dyn ProgramIo,
+    limits: ExecutionLimits,
+    monitor: Option<&mut dyn ExecutionMonitor>,
+) -> Result<(), ExecutionError> {
+    memory_managers.variable_memory.init_stack();
+    let result = execute_instructions(memory_managers, exit, io, limits, monitor);
+    //? Output written before an error should still be seen
+    let flushed = io.flush();
+    //? The stack is cleared even on error so the memory can be run again
+    memory_managers.variable_memory.clear_stack();
+    result?;
+
+    flushed.map_err(|error| ExecutionError {
+        error,
+        address: memory_managers.program_memory.memory.len(),
+    })
+}
+
+fn execute_instructions(
+    memory_managers: &mut MemoryManagers,
+    exit: &AtomicBool,
+    io: &mut dyn ProgramIo,
+    limits: ExecutionLimits,
+    mut monitor: Option<&mut dyn ExecutionMonitor>,
+) -> Result<(), ExecutionError> {
+    let mut pointer: usize = 0;
+    let program_length = memory_managers.program_memory.memory.len();
+    let start_time = Instant::now();
+    let mut executed: u64 = 0;
+
+    while pointer < program_length {
+        let instruction_address = pointer;
+        if let Some(monitor) = monitor.as_deref_mut() {
+            monitor.before_instruction(instruction_address, memory_managers);
+        }
+        let mut result = execute_instruction(&mut pointer, memory_managers, io);
+        if let Some(monitor) = monitor.as_deref_mut() {
+            monitor.after_instruction(instruction_address, memory_managers, &result);
+        }
+
+        executed += 1;
+        if result.is_ok() {
+            result = check_limits(exit, &limits, executed, start_time);
+        }
+
+        if let Err(error) = result {
+            return Err(ExecutionError {
+                error,
+                address: instruction_address,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks whether execution should stop after an instruction
+fn check_limits(
+    exit: &AtomicBool,
+    limits: &ExecutionLimits,
+    executed: u64,
+    start_time: Instant,
+) -> Result<(), RuntimeError> {
+    if exit.load(Ordering::Relaxed) {
+        return Err(RuntimeError::Interrupted);
+    }
+    if let Some(limit) = limits.max_instructions {
+        if executed > limit {
+            return Err(RuntimeError::InstructionLimitExceeded { limit });
+        }
+    }
+    if let Some(limit) = limits.max_time {
+        if start_time.elapsed() > limit {
+            return Err(RuntimeError::TimeLimitExceeded { limit });
+        }
+    }
+    Ok(())
+}
+
+/// Executes the instruction at `pointer`, moving `pointer` to the next instruction to execute
+pub fn execute_instruction(
+    pointer: &mut usize,
+    memory_managers: &mut MemoryManagers,
+    io: &mut dyn ProgramIo,
+) -> Result<(), RuntimeError> {
+    let code = memory_managers
+        .program_memory
+        .read(*pointer, INSTRUCTION_CODE_LENGTH)
+        .map_err(|_| RuntimeError::UnexpectedEndOfProgram { address: *pointer })?;
+    let code = u16::from_le_bytes(code.try_into().unwrap());
+    *pointer += INSTRUCTION_CODE_LENGTH;
+
+    match code {
+        0 => CopyInstruction::execute(pointer, memory_managers),
+        1 => InvertInstruction::execute(pointer, memory_managers),
+        2 => JumpIfNotInstruction::execute(pointer, memory_managers),
+        3 => JumpInstruction::execute(pointer, memory_managers),
+        4 => JumpVariableInstruction::execute(pointer, memory_managers),
+        5 => PrintInstruction::execute(pointer, memory_managers, io),
+        6 => AndInstruction::execute(pointer, memory_managers),
+        7 => EqualInstruction::execute(pointer, memory_managers),
+        8 => OrInstruction::execute(pointer, memory_managers),
+        9 => PrintCharsInstruction::execute(pointer, memory_managers, io),
+        10 => DynamicFromCopyInstruction::execute(pointer, memory_managers),
+        11 => DynamicToCopyInstruction::execute(pointer, memory_managers),
+        12 => JumpIfInstruction::execute(pointer, memory_managers),
+        13 => AddInstruction::execute(pointer, memory_managers),
+        14 => NotEqualInstruction::execute(pointer, memory_managers),
+        15 => InputInstruction::execute(pointer, memory_managers, io),
+        16 => SubtractInstruction::execute(pointer, memory_managers),
+        17 => MultiplyInstruction::execute(pointer, memory_managers),
+        18 => DivideInstruction::execute(pointer, memory_managers),
+        19 => ModuloInstruction::execute(pointer, memory_managers),
+        20 => GreaterInstruction::execute(pointer, memory_managers),
+        21 => GreaterEqualInstruction::execute(pointer, memory_managers),
+        22 => CallInstruction::execute(pointer, memory_managers),
+        23 => ReturnInstruction::execute(pointer, memory_managers),
+        24 => ReserveFrameInstruction::execute(pointer, memory_managers),
+        25 => ConcatInstruction::execute(pointer, memory_managers),
+        26 => StringIndexInstruction::execute(pointer, memory_managers),
+        27 => SliceInstruction::execute(pointer, memory_managers),
+        28 => PrintStringInstruction::execute(pointer, memory_managers, io),
+        code => Err(RuntimeError::UnknownOpcode {
+            code,
+            address: *pointer - INSTRUCTION_CODE_LENGTH,
+        }),
+    }
+}
@@ -1,6 +1,20 @@
+pub mod compiled_file;
+pub mod compiler;
+pub mod debug_info;
+pub mod debugger;
 pub mod errors;
 pub mod execution;
 pub mod memory_manager;
+pub mod optimiser;
 pub mod processing;
+pub mod profiler;
+pub mod program;
+pub mod program_io;
+pub mod trace;
 pub mod translator;
 pub mod util;
+pub mod vm;
+
+pub use compiler::Compiler;
+pub use program::Program;
+pub use vm::{CancellationToken, Vm};
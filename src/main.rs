@@ -1,17 +1,32 @@
 #![allow(dead_code)]
 
+mod compiled_file;
+mod compiler;
+mod debug_info;
+mod debugger;
 mod errors;
 mod execution;
 mod memory_manager;
+mod optimiser;
 mod processing;
+mod profiler;
+mod program;
+mod program_io;
+mod trace;
 mod translator;
 pub mod util;
+mod vm;
 
-use crate::execution::execute;
+use crate::debug_info::{DebugInfo, LineEntry};
+use crate::errors::Diagnostic;
+use crate::execution::{execute, execute_with_monitor};
+use crate::optimiser::optimise;
 use crate::processing::processor::MemoryManagers;
-#[allow(unused_imports)]
-use crate::translator::translate;
-use crate::util::info;
+use crate::profiler::Profiler;
+use crate::trace::{TraceFilter, Tracer};
+use crate::translator::translate_with_debug_info;
+use crate::util::{info, set_verbosity, Verbosity};
+use colored::Colorize;
 use processing::preprocessor::convert_to_symbols;
 use processing::processor::process_symbols;
 use std::env;
@@ -19,23 +34,207 @@ use std::ffi::OsStr;
 use std::fs;
 use std::mem::size_of;
 use std::path::Path;
+use std::process::ExitCode;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
 static CTRL_C: AtomicBool = AtomicBool::new(false);
 
-fn main() {
+const USAGE: &str = "\
+Usage: whython-4 [command] [file] [options]
+
+Commands:
+  run       Compile a .why file, or load a .cwhy file, and execute it (default)
+  build     Compile a .why file to a .cwhy file
+  check     Compile without writing or executing anything
+  disasm    Print the compiled instructions alongside their source lines
+  dump      Write the compiled variable and program memory to .b files
+  help      Print this message
+
+The file defaults to 'main.why'
+
+Options:
+  -o, --output <path>              Output path for 'build', or file name prefix for 'dump'
+  -O, --optimise                   Optimise the compiled instructions
+  -q, --quiet                      Only print program output and errors
+  -v, --verbose                    Also print [INFO] messages
+      --no-pause                   Exit without waiting for enter to be pressed
+      --max-stack-depth=<depth>    Maximum number of nested function calls
+      --debug                      Run under the interactive step debugger
+      --trace[=<file>]             Log each executed instruction to stderr or a file
+      --trace-addresses=<a>..<b>   Only trace instructions in an address range
+      --trace-opcodes=<code>,...   Only trace instructions with these codes
+      --profile                    Report where execution time goes
+      --profile-folded=<file>      Also write call stacks for flamegraph tools";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Command {
+    Run,
+    Build,
+    Check,
+    Disasm,
+    Dump,
+    Help,
+}
+
+impl Command {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "run" => Some(Command::Run),
+            "build" => Some(Command::Build),
+            "check" => Some(Command::Check),
+            "disasm" => Some(Command::Disasm),
+            "dump" => Some(Command::Dump),
+            "help" => Some(Command::Help),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed command line arguments
+struct Options {
+    command: Command,
+    input_file: String,
+    output: Option<String>,
+    optimise: bool,
+    verbosity: Verbosity,
+    pause: bool,
+    max_stack_depth: Option<usize>,
+    debug: bool,
+    /// `Some(None)` traces to stderr
+    trace: Option<Option<String>>,
+    trace_filter: TraceFilter,
+    /// `Some(Some(path))` also writes folded call stacks to `path`
+    profile: Option<Option<String>>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut args = args.iter();
+        let mut options = Options {
+            command: Command::Run,
+            input_file: "main.why".to_string(),
+            output: None,
+            optimise: false,
+            verbosity: Verbosity::Normal,
+            pause: true,
+            max_stack_depth: None,
+            debug: false,
+            trace: None,
+            trace_filter: TraceFilter::default(),
+            profile: None,
+        };
+
+        let mut input_file = None;
+        let mut command = None;
+        while let Some(arg) = args.next() {
+            //? The command is the first argument that isn't an option. A file on its own runs it,
+            //? as before there were commands
+            if command.is_none() && input_file.is_none() && !arg.starts_with('-') {
+                command = Command::from_name(arg);
+                if command.is_some() {
+                    continue;
+                }
+            }
+
+            match arg.as_str() {
+                "-h" | "--help" => options.command = Command::Help,
+                "-O" | "--optimise" => options.optimise = true,
+                "-q" | "--quiet" => options.verbosity = Verbosity::Quiet,
+                "-v" | "--verbose" => options.verbosity = Verbosity::Verbose,
+                "--no-pause" => options.pause = false,
+                "--debug" => options.debug = true,
+                "--trace" => options.trace = Some(None),
+                "--profile" => options.profile = Some(None),
+                "-o" | "--output" => match args.next() {
+                    Some(path) => options.output = Some(path.clone()),
+                    None => return Err(format!("Expected a path after '{}'", arg)),
+                },
+                _ => {
+                    if let Some(path) = arg.strip_prefix("--output=") {
+                        options.output = Some(path.to_string());
+                    } else if let Some(path) = arg.strip_prefix("--trace=") {
+                        options.trace = Some(Some(path.to_string()));
+                    } else if let Some(path) = arg.strip_prefix("--profile-folded=") {
+                        options.profile = Some(Some(path.to_string()));
+                    } else if let Some(addresses) = arg.strip_prefix("--trace-addresses=") {
+                        options.trace_filter.addresses = Some(
+                            TraceFilter::parse_addresses(addresses)
+                                .ok_or(format!("Invalid address range '{}'", addresses))?,
+                        );
+                    } else if let Some(opcodes) = arg.strip_prefix("--trace-opcodes=") {
+                        options.trace_filter.opcodes = Some(
+                            TraceFilter::parse_opcodes(opcodes)
+                                .ok_or(format!("Invalid instruction codes '{}'", opcodes))?,
+                        );
+                    } else if let Some(depth) = arg.strip_prefix("--max-stack-depth=") {
+                        options.max_stack_depth = Some(
+                            depth
+                                .parse()
+                                .map_err(|_| format!("Invalid maximum stack depth '{}'", depth))?,
+                        );
+                    } else if arg.starts_with('-') {
+                        return Err(format!("Unknown option '{}'", arg));
+                    } else if input_file.is_none() {
+                        input_file = Some(arg.clone());
+                    } else {
+                        return Err(format!("Unexpected argument '{}'", arg));
+                    }
+                }
+            }
+        }
+
+        if let Some(input_file) = input_file {
+            options.input_file = input_file;
+        }
+        //? '--help' takes precedence over any command
+        if options.command != Command::Help {
+            options.command = command.unwrap_or(Command::Run);
+        }
+
+        let modes = [
+            options.debug,
+            options.trace.is_some(),
+            options.profile.is_some(),
+        ];
+        if modes.iter().filter(|enabled| **enabled).count() > 1 {
+            return Err("Debugging, tracing and profiling can't be combined".to_string());
+        }
+        Ok(options)
+    }
+}
+
+fn main() -> ExitCode {
     ctrlc::set_handler(|| {
         CTRL_C.store(true, Ordering::Relaxed);
     })
     .expect("Error setting Ctrl-C handler");
 
-    wrapped_main(&CTRL_C);
-    util::pause();
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match Options::parse(&args) {
+        Err(e) => {
+            col_println!((red, bold), "{}", e);
+            println!("\n{}", USAGE);
+            return ExitCode::from(2);
+        }
+        Ok(options) => options,
+    };
+    set_verbosity(options.verbosity);
+
+    let result = wrapped_main(&options, &CTRL_C);
+    //? Help has nothing to read before exiting
+    if options.pause && options.command != Command::Help {
+        util::pause();
+    }
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(()) => ExitCode::FAILURE,
+    }
 }
 
-fn wrapped_main(exit: &AtomicBool) {
-    let args: Vec<String> = env::args().collect();
+/// Runs the command. Errors have already been printed when this fails
+fn wrapped_main(options: &Options, exit: &AtomicBool) -> Result<(), ()> {
     info(
         format!(
             "Platform pointer (usize) length: {} [{}-bit]",
@@ -45,103 +244,261 @@ fn wrapped_main(exit: &AtomicBool) {
         .as_str(),
     );
 
-    let input_file = if args.len() >= 2 {
-        args[1].clone()
-    } else {
-        "main.why".to_string()
-    };
-
-    let mut memory;
+    match options.command {
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Command::Build => {
+            let (memory, debug_info) = compile(&options.input_file, options.optimise)?;
+            let output = match &options.output {
+                Some(output) => output.clone(),
+                None => Path::new(&options.input_file)
+                    .with_extension("cwhy")
+                    .to_string_lossy()
+                    .to_string(),
+            };
+            memory
+                .save_to_compiled(&output, Some(&debug_info))
+                .map_err(|e| {
+                    col_println!((red, bold), "Failed to write '{}' - {}", output, e);
+                })
+        }
+        Command::Check => {
+            load(&options.input_file, options.optimise)?;
+            status_println!((green, bold), "No errors found");
+            Ok(())
+        }
+        Command::Disasm => {
+            let (memory, debug_info) = load(&options.input_file, options.optimise)?;
+            translate_with_debug_info(&memory.program_memory.memory, false, debug_info.as_ref());
+            Ok(())
+        }
+        Command::Dump => {
+            let (memory, _) = load(&options.input_file, options.optimise)?;
+            let prefix = match &options.output {
+                Some(output) => output.clone(),
+                None => Path::new(&options.input_file)
+                    .with_extension("")
+                    .to_string_lossy()
+                    .to_string(),
+            };
+            memory
+                .variable_memory
+                .dump_bytes(format!("{} - VariableMemory", prefix));
+            memory
+                .program_memory
+                .dump_bytes(format!("{} - ProgramMemory", prefix));
+            Ok(())
+        }
+        Command::Run => {
+            let (mut memory, debug_info) = load(&options.input_file, options.optimise)?;
+            run(&mut memory, debug_info.as_ref(), options, exit)
+        }
+    }
+}
 
-    let extension = match Path::new(&input_file).extension().and_then(OsStr::to_str) {
+/// Compiles a `.why` file or loads a `.cwhy` file
+fn load(input_file: &str, optimise: bool) -> Result<(MemoryManagers, Option<DebugInfo>), ()> {
+    match Path::new(input_file).extension().and_then(OsStr::to_str) {
+        Some("why") => {
+            let (memory, debug_info) = compile(input_file, optimise)?;
+            Ok((memory, Some(debug_info)))
+        }
+        Some("cwhy") => {
+            let (mut memory, mut debug_info) =
+                MemoryManagers::load_from_compiled_with_debug_info(input_file.to_string())
+                    .map_err(|e| {
+                        col_println!((red, bold), "Loading precompiled file failed - {}", e);
+                    })?;
+            if optimise {
+                optimise_program(&mut memory, debug_info.as_mut());
+            }
+            Ok((memory, debug_info))
+        }
+        Some(extension) => {
+            col_println!((red, bold), "Unrecognised extension '{}'", extension);
+            Err(())
+        }
         None => {
             col_println!((red, bold), "Invalid input file '{}'", input_file);
-            return;
+            Err(())
         }
-        Some(value) => value,
+    }
+}
+
+/// Compiles a `.why` file, printing any errors
+fn compile(input_file: &str, optimise: bool) -> Result<(MemoryManagers, DebugInfo), ()> {
+    let input = match fs::read_to_string(input_file) {
+        Err(e) => {
+            col_println!(
+                (red, bold),
+                "Error reading file '{}' - {}",
+                input_file,
+                e.to_string()
+            );
+            return Err(());
+        }
+        Ok(value) => value,
     };
 
-    //? Compile
-    if extension == "why" {
-        let input = match fs::read_to_string(&input_file) {
-            Err(e) => {
-                col_println!(
-                    (red, bold),
-                    "Error reading file '{}' - {}",
-                    input_file,
-                    e.to_string()
-                );
-                return;
-            }
-            Ok(value) => value,
-        };
+    status_println!(normal, "Starting compilation (pre)");
+    let start = Instant::now();
+    let r = match convert_to_symbols(&input) {
+        Err(e) => {
+            col_println!(
+                (red, bold),
+                "Compilation (pre) failed [{:?}]:",
+                start.elapsed()
+            );
+            print_diagnostics(&e, input_file, &input);
+            return Err(());
+        }
+        Ok(value) => value,
+    };
 
-        println!("Starting compilation (pre)");
-        let start = Instant::now();
-        let r = match convert_to_symbols(input) {
-            Err(e) => {
-                col_println!(
-                    (red, bold),
-                    "Compilation (pre) failed [{:?}]:\n\t{}",
-                    start.elapsed(),
-                    e
-                );
-                return;
-            }
-            Ok(value) => value,
-        };
+    status_println!(
+        (green, bold),
+        "Compilation (pre) completed [{:?}]",
+        start.elapsed()
+    );
+
+    status_println!(normal, "Starting compilation (post)");
+    let start = Instant::now();
+    let (mut memory, mut debug_info) = match process_symbols(r) {
+        Err(e) => {
+            col_println!(
+                (red, bold),
+                "Compilation (post) failed [{:?}]:",
+                start.elapsed()
+            );
+            print_diagnostics(&e, input_file, &input);
+            return Err(());
+        }
+        Ok(value) => value,
+    };
+
+    status_println!(
+        (green, bold),
+        "Compilation (post) completed [{:?}]",
+        start.elapsed()
+    );
 
-        col_println!(
-            (green, bold),
-            "Compilation (pre) completed [{:?}]",
-            start.elapsed()
-        );
+    debug_info.set_source(input_file, &input);
+    if optimise {
+        optimise_program(&mut memory, Some(&mut debug_info));
+    }
+    Ok((memory, debug_info))
+}
 
-        println!("Starting compilation (post)");
-        let start = Instant::now();
-        memory = match process_symbols(r) {
+/// Optimises compiled instructions, printing how much smaller they got
+fn optimise_program(memory: &mut MemoryManagers, debug_info: Option<&mut DebugInfo>) {
+    status_println!(normal, "Starting optimisation");
+    let start = Instant::now();
+    let report = optimise(memory, debug_info);
+    status_println!(
+        (green, bold),
+        "Optimisation completed [{:?}]",
+        start.elapsed()
+    );
+    status_println!(normal, "{}", report);
+}
+
+/// Executes a compiled program, under the debugger, tracer or profiler if requested
+fn run(
+    memory: &mut MemoryManagers,
+    debug_info: Option<&DebugInfo>,
+    options: &Options,
+    exit: &AtomicBool,
+) -> Result<(), ()> {
+    if let Some(depth) = options.max_stack_depth {
+        memory.variable_memory.max_stack_depth = depth;
+    }
+
+    let mut tracer = match &options.trace {
+        Some(Some(path)) => match Tracer::to_file(path, options.trace_filter.clone()) {
+            Ok(tracer) => Some(tracer),
             Err(e) => {
                 col_println!(
                     (red, bold),
-                    "Compilation (post) failed [{:?}]:\n\t{}",
-                    start.elapsed(),
+                    "Failed to create trace file '{}' - {}",
+                    path,
                     e
                 );
-                return;
+                return Err(());
             }
-            Ok(value) => value,
-        };
+        },
+        Some(None) => Some(Tracer::to_stderr(options.trace_filter.clone())),
+        None => None,
+    };
 
-        col_println!(
-            (green, bold),
-            "Compilation (post) completed [{:?}]",
-            start.elapsed()
-        );
+    let mut profiler = match &options.profile {
+        Some(Some(_)) => Some(Profiler::with_stacks()),
+        Some(None) => Some(Profiler::new()),
+        None => None,
+    };
 
-        memory.save_to_compiled("Compiled".to_string());
-    }
-    //? Load compiled file
-    else if extension == "cwhy" {
-        memory = match MemoryManagers::load_from_compiled(input_file) {
-            Err(e) => {
-                col_println!((red, bold), "Loading precompiled file failed - {}", e);
-                return;
-            }
-            Ok(value) => value,
-        };
+    let result = if options.debug {
+        debugger::debug(memory, debug_info, exit)
+    } else if let Some(tracer) = &mut tracer {
+        execute_with_monitor(memory, exit, Some(tracer))
+    } else if let Some(profiler) = &mut profiler {
+        execute_with_monitor(memory, exit, Some(profiler))
     } else {
-        col_println!((red, bold), "Unrecognised extension '{}'", extension);
-        return;
+        execute(memory, exit)
+    };
+    //? Flush the trace before reporting errors
+    drop(tracer);
+
+    if let Some(profiler) = &profiler {
+        profiler.print_report(&memory.program_memory.memory, debug_info, 20);
+        if let Some(Some(path)) = &options.profile {
+            match profiler.write_folded_stacks(path, debug_info) {
+                Ok(()) => col_println!(green, "Call stacks written to '{}'", path),
+                Err(e) => col_println!((red, bold), "Failed to write call stacks - {}", e),
+            }
+        }
     }
 
-    // translate(&memory.program_memory.memory);
+    result.map_err(|e| {
+        col_println!((red, bold), "Execution failed:\n\t{}", e);
+        if let Some(debug_info) = debug_info {
+            if let Some(line) = debug_info.get_line(e.address) {
+                print_source_line(debug_info, line);
+            }
+        }
+    })
+}
 
-    //? memory.variable_memory.dump_bytes("VariableMemory".to_string());
-    //? memory.program_memory.dump_bytes("ProgramMemory".to_string());
+/// Prints the source line a runtime error occurred on
+fn print_source_line(debug_info: &DebugInfo, line: &LineEntry) {
+    let line_number = (line.line + 1).to_string();
+    let gutter = " ".repeat(line_number.len());
+    println!(
+        "{}{} {}:{}",
+        gutter,
+        "-->".blue().bold(),
+        debug_info.file,
+        line_number
+    );
+    println!("{} {}", gutter, "|".blue().bold());
+    println!(
+        "{} {} {}",
+        line_number.blue().bold(),
+        "|".blue().bold(),
+        line.text
+    );
+}
 
-    if let Err(e) = execute(&mut memory, exit) {
-        col_println!((red, bold), "Execution failed:\n\t{}", e)
+/// Prints compilation errors with the offending source followed by how many there were
+fn print_diagnostics(diagnostics: &[Diagnostic], file: &str, source: &str) {
+    for diagnostic in diagnostics {
+        println!("{}\n", diagnostic.clone().with_file(file).render(source));
     }
-
-    //? memory.variable_memory.dump_bytes("VariableMemory - post".to_string());
+    col_println!(
+        (red, bold),
+        "{} error{}",
+        diagnostics.len(),
+        if diagnostics.len() == 1 { "" } else { "s" }
+    );
 }
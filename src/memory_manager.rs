@@ -1,21 +1,63 @@
+use crate::errors::RuntimeError;
+use crate::util::{encode_usize, get_usize, POINTER_SIZE};
 use num_format::{Locale, ToFormattedString};
+use std::cell::RefCell;
 use std::fs;
 use std::io::Write;
 
+/// Set on variable addresses that are relative to the current stack frame
+pub const FRAME_RELATIVE_FLAG: usize = 1 << (usize::BITS - 1);
+
+/// Size of the return address and saved frame pointer at the start of every stack frame
+pub const FRAME_HEADER_SIZE: usize = POINTER_SIZE * 2;
+
+/// Default maximum number of nested function calls before a stack overflow
+pub const DEFAULT_MAX_STACK_DEPTH: usize = 10_000;
+
+/// Default maximum size of variable memory while executing, in bytes
+pub const DEFAULT_MAX_MEMORY: usize = 1 << 30;
+
+/// A read or write of memory recorded while access logging is enabled
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MemoryAccess {
+    Read { address: usize, data: Vec<u8> },
+    Write { address: usize, data: Vec<u8> },
+}
+
 #[derive(Default)]
 pub struct MemoryManager {
     pub memory: Vec<u8>,
+    /// Sizes of the stack frames being allocated into while compiling (innermost last)
+    frames: Vec<usize>,
+    /// Start of the current stack frame while executing
+    frame_pointer: usize,
+    /// Start of the stack while executing - everything before is static memory
+    stack_base: usize,
+    stack_depth: usize,
+    pub max_stack_depth: usize,
+    pub max_memory: usize,
+    /// Accesses recorded since `start_access_log` - `None` when not logging
+    access_log: RefCell<Option<Vec<MemoryAccess>>>,
 }
 
 impl MemoryManager {
     /// Creates an empty memory manager
     pub fn new() -> Self {
-        Self { memory: Vec::new() }
+        Self::from_vec(Vec::new())
     }
 
     /// Creates memory manager from vector of bytes
     pub fn from_vec(memory: Vec<u8>) -> Self {
-        Self { memory }
+        Self {
+            memory,
+            frames: Vec::new(),
+            frame_pointer: 0,
+            stack_base: 0,
+            stack_depth: 0,
+            max_stack_depth: DEFAULT_MAX_STACK_DEPTH,
+            max_memory: DEFAULT_MAX_MEMORY,
+            access_log: RefCell::new(None),
+        }
     }
 
     /// Gets the position after the last piece of memory written
@@ -44,6 +86,86 @@ impl MemoryManager {
         }
     }
 
+    /// Gets `length` bytes at `address`. Fails if any of them are outside of memory
+    pub fn read(&self, address: usize, length: usize) -> Result<&[u8], RuntimeError> {
+        let data = self.get(address, length)?;
+        self.log_access(|| MemoryAccess::Read {
+            address,
+            data: data.to_vec(),
+        });
+        Ok(data)
+    }
+
+    fn get(&self, address: usize, length: usize) -> Result<&[u8], RuntimeError> {
+        address
+            .checked_add(length)
+            .and_then(|end| self.memory.get(address..end))
+            .ok_or(RuntimeError::InvalidMemoryAccess { address, length })
+    }
+
+    /// Gets the byte at `address`
+    pub fn read_byte(&self, address: usize) -> Result<u8, RuntimeError> {
+        Ok(self.read(address, 1)?[0])
+    }
+
+    /// Gets the `usize` at `address`
+    pub fn read_usize(&self, address: usize) -> Result<usize, RuntimeError> {
+        Ok(get_usize(&0, self.read(address, POINTER_SIZE)?))
+    }
+
+    /// Overwrites the memory at `address`. Fails without writing anything if any of it is outside
+    /// of memory
+    pub fn write(&mut self, address: usize, data: &[u8]) -> Result<(), RuntimeError> {
+        let length = data.len();
+        address
+            .checked_add(length)
+            .and_then(|end| self.memory.get_mut(address..end))
+            .ok_or(RuntimeError::InvalidMemoryAccess { address, length })?
+            .copy_from_slice(data);
+        self.log_access(|| MemoryAccess::Write {
+            address,
+            data: data.to_vec(),
+        });
+        Ok(())
+    }
+
+    /// Sets the byte at `address`
+    pub fn write_byte(&mut self, address: usize, value: u8) -> Result<(), RuntimeError> {
+        self.write(address, &[value])
+    }
+
+    /// Copies `length` bytes from `from` to `to`. The regions may overlap
+    pub fn copy(&mut self, from: usize, to: usize, length: usize) -> Result<(), RuntimeError> {
+        self.get(from, length)?;
+        self.get(to, length)?;
+        self.memory.copy_within(from..from + length, to);
+        self.log_access(|| MemoryAccess::Read {
+            address: from,
+            data: self.memory[to..to + length].to_vec(),
+        });
+        self.log_access(|| MemoryAccess::Write {
+            address: to,
+            data: self.memory[to..to + length].to_vec(),
+        });
+        Ok(())
+    }
+
+    /// Starts recording every read and write made through this memory manager
+    pub fn start_access_log(&self) {
+        *self.access_log.borrow_mut() = Some(Vec::new());
+    }
+
+    /// Stops recording accesses. Returns those recorded since `start_access_log`
+    pub fn take_access_log(&self) -> Vec<MemoryAccess> {
+        self.access_log.borrow_mut().take().unwrap_or_default()
+    }
+
+    fn log_access(&self, access: impl FnOnce() -> MemoryAccess) {
+        if let Some(log) = self.access_log.borrow_mut().as_mut() {
+            log.push(access());
+        }
+    }
+
     /// Reserves a section of memory. Returns the position of this memory
     pub fn reserve(&mut self, amount: usize) -> usize {
         let position = self.get_position();
@@ -54,6 +176,137 @@ impl MemoryManager {
         position
     }
 
+    /// Starts allocating variables in a new stack frame
+    pub fn enter_frame(&mut self) {
+        self.frames.push(FRAME_HEADER_SIZE);
+    }
+
+    /// Stops allocating variables in the current stack frame. Returns the size of the frame
+    pub fn exit_frame(&mut self) -> usize {
+        self.frames
+            .pop()
+            .expect("Exited frame when no frame was entered")
+    }
+
+    /// Temporarily stops allocating in stack frames so static memory can be allocated.
+    /// Frames must be restored with `resume_frames`
+    pub fn suspend_frames(&mut self) -> Vec<usize> {
+        std::mem::take(&mut self.frames)
+    }
+
+    /// Restores frames removed by `suspend_frames`
+    pub fn resume_frames(&mut self, frames: Vec<usize>) {
+        self.frames = frames;
+    }
+
+    /// Allocates space for a variable. Returns a frame-relative address if inside a stack frame,
+    /// otherwise reserves static memory
+    pub fn allocate(&mut self, amount: usize) -> usize {
+        match self.frames.last_mut() {
+            Some(frame_size) => {
+                let position = *frame_size;
+                *frame_size += amount;
+                position | FRAME_RELATIVE_FLAG
+            }
+            None => self.reserve(amount),
+        }
+    }
+
+    /// Resets the call stack to begin after the current contents of memory
+    pub fn init_stack(&mut self) {
+        self.stack_base = self.memory.len();
+        self.frame_pointer = self.stack_base;
+        self.stack_depth = 0;
+    }
+
+    /// Removes any stack frames left in memory
+    pub fn clear_stack(&mut self) {
+        self.memory.truncate(self.stack_base);
+        self.frame_pointer = self.stack_base;
+        self.stack_depth = 0;
+    }
+
+    /// Converts a frame-relative address into an absolute one. Static addresses are unchanged
+    pub fn resolve(&self, address: usize) -> usize {
+        if address & FRAME_RELATIVE_FLAG != 0 {
+            self.frame_pointer
+                .wrapping_add(address & !FRAME_RELATIVE_FLAG)
+        } else {
+            address
+        }
+    }
+
+    /// Gets the number of stack frames pushed while executing
+    pub fn get_stack_depth(&self) -> usize {
+        self.stack_depth
+    }
+
+    /// Pushes a stack frame containing the return address and the previous frame pointer
+    pub fn push_frame(&mut self, return_address: usize) -> Result<(), RuntimeError> {
+        if self.stack_depth >= self.max_stack_depth {
+            return Err(RuntimeError::StackOverflow {
+                max_depth: self.max_stack_depth,
+            });
+        }
+        self.stack_depth += 1;
+
+        let frame_pointer = self.memory.len();
+        self.memory.extend(encode_usize(return_address));
+        self.memory.extend(encode_usize(self.frame_pointer));
+        self.frame_pointer = frame_pointer;
+        self.log_access(|| MemoryAccess::Write {
+            address: frame_pointer,
+            data: self.memory[frame_pointer..].to_vec(),
+        });
+        Ok(())
+    }
+
+    /// Pops the current stack frame. Returns the return address
+    pub fn pop_frame(&mut self) -> Result<usize, RuntimeError> {
+        if self.stack_depth == 0 {
+            return Err(RuntimeError::StackUnderflow);
+        }
+        self.stack_depth -= 1;
+
+        let frame_pointer = self.frame_pointer;
+        let return_address = self.read_usize(frame_pointer)?;
+        self.frame_pointer = self.read_usize(frame_pointer + POINTER_SIZE)?;
+        self.memory.truncate(frame_pointer);
+        Ok(return_address)
+    }
+
+    /// Gets the return address of every stack frame, innermost first
+    pub fn get_return_addresses(&self) -> Vec<usize> {
+        let mut return_addresses = Vec::new();
+        let mut frame_pointer = self.frame_pointer;
+        for _ in 0..self.stack_depth {
+            match (
+                self.read_usize(frame_pointer),
+                self.read_usize(frame_pointer + POINTER_SIZE),
+            ) {
+                (Ok(return_address), Ok(previous)) => {
+                    return_addresses.push(return_address);
+                    frame_pointer = previous;
+                }
+                _ => break,
+            }
+        }
+        return_addresses
+    }
+
+    /// Adds `amount` zeroed bytes to the end of the stack
+    pub fn grow_stack(&mut self, amount: usize) -> Result<(), RuntimeError> {
+        let size = self.memory.len().saturating_add(amount);
+        if size > self.max_memory {
+            return Err(RuntimeError::OutOfMemory {
+                size,
+                max_memory: self.max_memory,
+            });
+        }
+        self.memory.resize(size, 0);
+        Ok(())
+    }
+
     /// Saves the bytes in a '`name.b`' file
     pub fn dump_bytes(&self, name: String) {
         let name = name + ".b";
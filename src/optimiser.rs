@@ -0,0 +1,721 @@
+use crate::debug_info::{DebugInfo, LineEntry};
+use crate::memory_manager::{FRAME_HEADER_SIZE, FRAME_RELATIVE_FLAG};
+use crate::processing::instructions::add_instruction_13::ADD_INSTRUCTION_CODE;
+use crate::processing::instructions::and_instruction_6::AND_INSTRUCTION_CODE;
+use crate::processing::instructions::call_instruction_22::CALL_INSTRUCTION_CODE;
+use crate::processing::instructions::concat_instruction_25::CONCAT_INSTRUCTION_CODE;
+use crate::processing::instructions::copy_instruction_0::COPY_INSTRUCTION_CODE;
+use crate::processing::instructions::divide_instruction_18::DIVIDE_INSTRUCTION_CODE;
+use crate::processing::instructions::dynamic_from_copy_instruction_10::DYNAMIC_FROM_COPY_INSTRUCTION_CODE;
+use crate::processing::instructions::dynamic_to_copy_instruction_11::DYNAMIC_TO_COPY_INSTRUCTION_CODE;
+use crate::processing::instructions::equal_instruction_7::EQUAL_INSTRUCTION_CODE;
+use crate::processing::instructions::greater_equal_instruction_21::GREATER_EQUAL_INSTRUCTION_CODE;
+use crate::processing::instructions::greater_instruction_20::GREATER_INSTRUCTION_CODE;
+use crate::processing::instructions::input_instruction_15::INPUT_INSTRUCTION_CODE;
+use crate::processing::instructions::invert_instruction_1::INVERT_INSTRUCTION_CODE;
+use crate::processing::instructions::jump_if_instruction_12::JUMP_IF_INSTRUCTION_CODE;
+use crate::processing::instructions::jump_if_not_instruction_2::JUMP_IF_NOT_INSTRUCTION_CODE;
+use crate::processing::instructions::jump_instruction_3::JUMP_INSTRUCTION_CODE;
+use crate::processing::instructions::jump_variable_instruction_4::JUMP_VARIABLE_INSTRUCTION_CODE;
+use crate::processing::instructions::modulo_instruction_19::MODULO_INSTRUCTION_CODE;
+use crate::processing::instructions::multiply_instruction_17::MULTIPLY_INSTRUCTION_CODE;
+use crate::processing::instructions::not_equal_instruction_14::NOT_EQUAL_INSTRUCTION_CODE;
+use crate::processing::instructions::or_instruction_8::OR_INSTRUCTION_CODE;
+use crate::processing::instructions::print_chars_instruction_9::PRINT_CHARS_INSTRUCTION_CODE;
+use crate::processing::instructions::print_instruction_5::PRINT_INSTRUCTION_CODE;
+use crate::processing::instructions::print_string_instruction_28::PRINT_STRING_INSTRUCTION_CODE;
+use crate::processing::instructions::reserve_frame_instruction_24::RESERVE_FRAME_INSTRUCTION_CODE;
+use crate::processing::instructions::return_instruction_23::RETURN_INSTRUCTION_CODE;
+use crate::processing::instructions::slice_instruction_27::SLICE_INSTRUCTION_CODE;
+use crate::processing::instructions::string_index_instruction_26::STRING_INDEX_INSTRUCTION_CODE;
+use crate::processing::instructions::subtract_instruction_16::SUBTRACT_INSTRUCTION_CODE;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
+use crate::processing::processor::MemoryManagers;
+use crate::translator::get_instruction_size;
+use crate::util::{encode_usize, get_usize, POINTER_SIZE};
+use num_format::{Locale, ToFormattedString};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+
+/// What `optimise` changed
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct OptimisationReport {
+    /// Size of program memory before optimising, in bytes
+    pub original_size: usize,
+    /// Size of program memory after optimising, in bytes
+    pub optimised_size: usize,
+    /// Copies to themselves or to memory that is never read
+    pub copies_removed: usize,
+    /// Jumps to other jumps redirected to the final destination
+    pub jumps_threaded: usize,
+    /// Jumps to the instruction following them
+    pub jumps_removed: usize,
+    /// Inversions followed by a jump if not, replaced with a jump if
+    pub inversions_folded: usize,
+    /// Reads of a copy of a constant changed to read the constant itself
+    pub reads_forwarded: usize,
+}
+
+impl OptimisationReport {
+    /// Gets the number of bytes removed from program memory
+    pub fn get_size_reduction(&self) -> usize {
+        self.original_size - self.optimised_size
+    }
+}
+
+impl fmt::Display for OptimisationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percentage = if self.original_size == 0 {
+            0.0
+        } else {
+            self.get_size_reduction() as f64 * 100.0 / self.original_size as f64
+        };
+        write!(
+            f,
+            "Program memory reduced from {} to {} bytes (-{:.1}%) - {} copies removed, {} jumps \
+            threaded, {} jumps removed, {} inversions folded, {} reads forwarded",
+            self.original_size.to_formatted_string(&Locale::en),
+            self.optimised_size.to_formatted_string(&Locale::en),
+            percentage,
+            self.copies_removed,
+            self.jumps_threaded,
+            self.jumps_removed,
+            self.inversions_folded,
+            self.reads_forwarded
+        )
+    }
+}
+
+/// An instruction decoded from program memory
+struct DecodedInstruction {
+    /// Address in the unoptimised program
+    address: usize,
+    code: u16,
+    data: Vec<u8>,
+    removed: bool,
+}
+
+impl DecodedInstruction {
+    fn get_size(&self) -> usize {
+        INSTRUCTION_CODE_LENGTH + self.data.len()
+    }
+
+    fn get_operand(&self, index: usize) -> usize {
+        get_usize(&(index * POINTER_SIZE), &self.data)
+    }
+
+    fn set_operand(&mut self, index: usize, value: usize) {
+        self.data[index * POINTER_SIZE..(index + 1) * POINTER_SIZE]
+            .copy_from_slice(&encode_usize(value));
+    }
+
+    /// Gets the index of the operand holding the program address the instruction may jump to
+    fn get_destination_operand(&self) -> Option<usize> {
+        match self.code {
+            JUMP_INSTRUCTION_CODE | CALL_INSTRUCTION_CODE => Some(0),
+            JUMP_IF_INSTRUCTION_CODE | JUMP_IF_NOT_INSTRUCTION_CODE => Some(1),
+            _ => None,
+        }
+    }
+
+    fn get_destination(&self) -> Option<usize> {
+        self.get_destination_operand()
+            .map(|operand| self.get_operand(operand))
+    }
+
+    /// Returns `true` for jumps that don't push a stack frame
+    fn is_jump(&self) -> bool {
+        matches!(
+            self.code,
+            JUMP_INSTRUCTION_CODE | JUMP_IF_INSTRUCTION_CODE | JUMP_IF_NOT_INSTRUCTION_CODE
+        )
+    }
+
+    /// Gets the operands holding the start of a read of a fixed length, along with the length.
+    /// Changing one of these operands moves the whole read
+    fn get_read_operands(&self) -> Vec<(usize, usize)> {
+        let operand = |index: usize| self.get_operand(index);
+
+        match self.code {
+            COPY_INSTRUCTION_CODE => vec![(0, operand(2))],
+            INVERT_INSTRUCTION_CODE | JUMP_IF_INSTRUCTION_CODE | JUMP_IF_NOT_INSTRUCTION_CODE => {
+                vec![(0, 1)]
+            }
+            JUMP_VARIABLE_INSTRUCTION_CODE => vec![(0, POINTER_SIZE)],
+            PRINT_INSTRUCTION_CODE | PRINT_CHARS_INSTRUCTION_CODE => {
+                vec![(0, operand(1).saturating_mul(operand(2)))]
+            }
+            AND_INSTRUCTION_CODE | OR_INSTRUCTION_CODE => vec![(0, 1), (1, 1)],
+            EQUAL_INSTRUCTION_CODE
+            | NOT_EQUAL_INSTRUCTION_CODE
+            | ADD_INSTRUCTION_CODE
+            | SUBTRACT_INSTRUCTION_CODE
+            | MULTIPLY_INSTRUCTION_CODE
+            | DIVIDE_INSTRUCTION_CODE
+            | MODULO_INSTRUCTION_CODE
+            | GREATER_INSTRUCTION_CODE
+            | GREATER_EQUAL_INSTRUCTION_CODE => vec![(0, operand(2)), (1, operand(2))],
+            DYNAMIC_FROM_COPY_INSTRUCTION_CODE => vec![(2, POINTER_SIZE)],
+            DYNAMIC_TO_COPY_INSTRUCTION_CODE => vec![(2, POINTER_SIZE), (3, operand(4))],
+            STRING_INDEX_INSTRUCTION_CODE => vec![(1, POINTER_SIZE)],
+            SLICE_INSTRUCTION_CODE => vec![(1, POINTER_SIZE), (2, POINTER_SIZE)],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Gets the variable memory read by the instruction. Addresses may be frame relative, which
+    /// never overlap absolute addresses as the stack is after all static memory
+    fn get_reads(&self) -> Vec<Range<usize>> {
+        let operand = |index: usize| self.get_operand(index);
+        //? Strings are read up to their length at runtime, so assume everything after them is
+        let unbounded = |start: usize| {
+            if start & FRAME_RELATIVE_FLAG != 0 {
+                start..usize::MAX
+            } else {
+                start..FRAME_RELATIVE_FLAG
+            }
+        };
+
+        let mut reads: Vec<Range<usize>> = self
+            .get_read_operands()
+            .into_iter()
+            .map(|(index, length)| bounded(operand(index), length))
+            .collect();
+        match self.code {
+            //? The element read depends on the index at runtime, so assume the whole array is
+            DYNAMIC_FROM_COPY_INSTRUCTION_CODE => reads.push(bounded(
+                operand(0),
+                operand(1)
+                    .saturating_mul(operand(5))
+                    .saturating_add(operand(4)),
+            )),
+            //? Reads the return address and previous frame pointer
+            RETURN_INSTRUCTION_CODE => reads.push(bounded(FRAME_RELATIVE_FLAG, FRAME_HEADER_SIZE)),
+            CONCAT_INSTRUCTION_CODE => reads.extend([unbounded(operand(0)), unbounded(operand(1))]),
+            STRING_INDEX_INSTRUCTION_CODE
+            | SLICE_INSTRUCTION_CODE
+            | PRINT_STRING_INSTRUCTION_CODE => reads.push(unbounded(operand(0))),
+            _ => {}
+        }
+        reads
+    }
+
+    /// Gets the variable memory written by the instruction, apart from the stack frames pushed by
+    /// calls and reserved by `ReserveFrame`
+    fn get_writes(&self) -> Vec<Range<usize>> {
+        let operand = |index: usize| self.get_operand(index);
+
+        match self.code {
+            COPY_INSTRUCTION_CODE => vec![bounded(operand(1), operand(2))],
+            INVERT_INSTRUCTION_CODE => vec![bounded(operand(1), 1)],
+            AND_INSTRUCTION_CODE | OR_INSTRUCTION_CODE | STRING_INDEX_INSTRUCTION_CODE => {
+                vec![bounded(operand(2), 1)]
+            }
+            EQUAL_INSTRUCTION_CODE
+            | NOT_EQUAL_INSTRUCTION_CODE
+            | GREATER_INSTRUCTION_CODE
+            | GREATER_EQUAL_INSTRUCTION_CODE => vec![bounded(operand(3), 1)],
+            ADD_INSTRUCTION_CODE
+            | SUBTRACT_INSTRUCTION_CODE
+            | MULTIPLY_INSTRUCTION_CODE
+            | DIVIDE_INSTRUCTION_CODE
+            | MODULO_INSTRUCTION_CODE => vec![bounded(operand(3), operand(2))],
+            DYNAMIC_FROM_COPY_INSTRUCTION_CODE => vec![bounded(operand(3), operand(4))],
+            DYNAMIC_TO_COPY_INSTRUCTION_CODE => vec![bounded(
+                operand(0),
+                operand(1)
+                    .saturating_mul(operand(5))
+                    .saturating_add(operand(4)),
+            )],
+            //? Strings are stored after their length
+            INPUT_INSTRUCTION_CODE if operand(2) != 0 => {
+                vec![bounded(operand(1), POINTER_SIZE.saturating_add(operand(0)))]
+            }
+            INPUT_INSTRUCTION_CODE => vec![bounded(operand(1), operand(0))],
+            CONCAT_INSTRUCTION_CODE => {
+                vec![bounded(operand(2), POINTER_SIZE.saturating_add(operand(3)))]
+            }
+            SLICE_INSTRUCTION_CODE => {
+                vec![bounded(operand(3), POINTER_SIZE.saturating_add(operand(4)))]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the instruction after this one is only run by jumping to it, or runs
+    /// with a different stack frame
+    fn ends_straight_line(&self) -> bool {
+        matches!(
+            self.code,
+            JUMP_INSTRUCTION_CODE
+                | CALL_INSTRUCTION_CODE
+                | RETURN_INSTRUCTION_CODE
+                | RESERVE_FRAME_INSTRUCTION_CODE
+        )
+    }
+}
+
+fn bounded(start: usize, length: usize) -> Range<usize> {
+    start..start.saturating_add(length)
+}
+
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// The instructions reading or writing each part of variable memory, built once so that finding
+/// what accesses a range doesn't need every instruction to be checked
+struct AccessIndex {
+    /// Bounded accesses and the index of the instruction making them, sorted by start
+    bounded: Vec<(Range<usize>, usize)>,
+    /// Length of the longest bounded access
+    longest: usize,
+    /// Accesses up to the end of static or frame memory e.g. reads of strings
+    unbounded: Vec<(Range<usize>, usize)>,
+}
+
+impl AccessIndex {
+    /// Indexes the accesses of every instruction that hasn't been removed
+    fn new(
+        instructions: &[DecodedInstruction],
+        get_accesses: fn(&DecodedInstruction) -> Vec<Range<usize>>,
+    ) -> Self {
+        let mut bounded = Vec::new();
+        let mut unbounded = Vec::new();
+        for (i, instruction) in instructions.iter().enumerate() {
+            if instruction.removed {
+                continue;
+            }
+            for access in get_accesses(instruction) {
+                if access.end == FRAME_RELATIVE_FLAG || access.end == usize::MAX {
+                    unbounded.push((access, i));
+                } else {
+                    bounded.push((access, i));
+                }
+            }
+        }
+        bounded.sort_unstable_by_key(|(access, _)| access.start);
+        let longest = bounded
+            .iter()
+            .map(|(access, _)| access.len())
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            bounded,
+            longest,
+            unbounded,
+        }
+    }
+
+    /// Gets the index of every instruction accessing any of `range`. Instructions may appear more
+    /// than once and may have been removed since the index was built
+    fn get_accessors(&self, range: &Range<usize>) -> Vec<usize> {
+        //? Accesses starting at or after the end of the range can't overlap it, and neither can
+        //? accesses starting more than `longest` before it
+        let end = self
+            .bounded
+            .partition_point(|(access, _)| access.start < range.end);
+        self.bounded[..end]
+            .iter()
+            .rev()
+            .take_while(|(access, _)| access.start.saturating_add(self.longest) > range.start)
+            .chain(&self.unbounded)
+            .filter(|(access, _)| overlaps(access, range))
+            .map(|(_, i)| *i)
+            .collect()
+    }
+}
+
+/// Splits program memory into instructions. Returns `None` if it contains anything other than
+/// complete, valid instructions
+fn decode(program: &[u8]) -> Option<Vec<DecodedInstruction>> {
+    let mut instructions = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        let code = program.get(address..address + INSTRUCTION_CODE_LENGTH)?;
+        let code = u16::from_le_bytes(code.try_into().unwrap());
+        let start = address + INSTRUCTION_CODE_LENGTH;
+        let end = start + get_instruction_size(code)?;
+        instructions.push(DecodedInstruction {
+            address,
+            code,
+            data: program.get(start..end)?.to_vec(),
+            removed: false,
+        });
+        address = end;
+    }
+    Some(instructions)
+}
+
+struct Optimiser {
+    instructions: Vec<DecodedInstruction>,
+    /// Size of the unoptimised program
+    end: usize,
+}
+
+impl Optimiser {
+    /// Gets the index of the instruction at the address, or of the end of the program
+    fn get_index(&self, address: usize) -> usize {
+        self.instructions
+            .partition_point(|instruction| instruction.address < address)
+    }
+
+    /// Gets the address of the first instruction executed when jumping to `address`, skipping
+    /// removed instructions
+    fn resolve(&self, address: usize) -> usize {
+        self.instructions[self.get_index(address)..]
+            .iter()
+            .find(|instruction| !instruction.removed)
+            .map_or(self.end, |instruction| instruction.address)
+    }
+
+    /// Returns `true` if every jump and call goes to the start of an instruction or the end of the
+    /// program, so that they can be relocated
+    fn has_valid_destinations(&self) -> bool {
+        self.instructions
+            .iter()
+            .filter_map(|instruction| instruction.get_destination())
+            .all(|destination| {
+                destination == self.end
+                    || self
+                        .instructions
+                        .get(self.get_index(destination))
+                        .is_some_and(|instruction| instruction.address == destination)
+            })
+    }
+
+    /// Gets the address of every jump and call destination
+    fn get_destinations(&self) -> HashSet<usize> {
+        self.instructions
+            .iter()
+            .filter(|instruction| !instruction.removed)
+            .filter_map(|instruction| instruction.get_destination())
+            .collect()
+    }
+
+    /// Returns `true` if any instruction other than those at `excluding` reads any of `range`
+    fn is_read(&self, reads: &AccessIndex, range: &Range<usize>, excluding: &[usize]) -> bool {
+        reads
+            .get_accessors(range)
+            .into_iter()
+            .any(|i| !self.instructions[i].removed && !excluding.contains(&i))
+    }
+
+    /// Removes copies from an address to itself
+    fn remove_self_copies(&mut self) -> usize {
+        let mut removed = 0;
+        for instruction in self.instructions.iter_mut() {
+            if instruction.code == COPY_INSTRUCTION_CODE
+                && (instruction.get_operand(0) == instruction.get_operand(1)
+                    || instruction.get_operand(2) == 0)
+            {
+                instruction.removed = true;
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Replaces an `Invert` followed by a `JumpIfNot` on the inverted value with a `JumpIf` on
+    /// the original value, if nothing else reads the inverted value
+    fn fold_inversions(&mut self) -> usize {
+        //? Folding keeps both the reads and the destinations of the instructions it replaces
+        let reads = AccessIndex::new(&self.instructions, DecodedInstruction::get_reads);
+        let destinations = self.get_destinations();
+        let mut folded = 0;
+        for i in 1..self.instructions.len() {
+            let (invert, jump) = (&self.instructions[i - 1], &self.instructions[i]);
+            if invert.removed
+                || jump.removed
+                || invert.code != INVERT_INSTRUCTION_CODE
+                || jump.code != JUMP_IF_NOT_INSTRUCTION_CODE
+                || jump.get_operand(0) != invert.get_operand(1)
+            {
+                continue;
+            }
+            //? Jumping straight to the `JumpIfNot` would skip the inversion
+            if destinations.contains(&jump.address) {
+                continue;
+            }
+            let inverted = invert.get_operand(1);
+            if self.is_read(&reads, &(inverted..inverted + 1), &[i - 1, i]) {
+                continue;
+            }
+
+            let (condition, destination) = (invert.get_operand(0), jump.get_operand(1));
+            let invert = &mut self.instructions[i - 1];
+            invert.code = JUMP_IF_INSTRUCTION_CODE;
+            invert.set_operand(0, condition);
+            invert.set_operand(1, destination);
+            self.instructions[i].removed = true;
+            folded += 1;
+        }
+        folded
+    }
+
+    /// Changes reads of memory copied from a constant to read the constant instead, until the
+    /// copy is overwritten or the program could have jumped there from elsewhere, so that
+    /// `remove_dead_copies` can remove copies that are no longer read
+    fn forward_constant_copies(&mut self) -> usize {
+        let writes = AccessIndex::new(&self.instructions, DecodedInstruction::get_writes);
+        let destinations = self.get_destinations();
+        // Start of each range currently holding a copy of a constant, along with its end and the
+        // address of the constant. Ranges never overlap as a copy overwrites any it overlaps
+        let mut copies: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+        let mut forwarded = 0;
+
+        for instruction in self.instructions.iter_mut() {
+            //? Removed jump destinations still mark where the jump lands
+            if destinations.contains(&instruction.address) {
+                copies.clear();
+            }
+            if instruction.removed {
+                continue;
+            }
+
+            for (operand, length) in instruction.get_read_operands() {
+                let read = bounded(instruction.get_operand(operand), length);
+                let constant = match copies.range(..=read.start).next_back() {
+                    Some((&start, &(end, constant))) if read.end <= end && !read.is_empty() => {
+                        constant + (read.start - start)
+                    }
+                    _ => continue,
+                };
+                instruction.set_operand(operand, constant);
+                forwarded += 1;
+            }
+
+            for write in instruction.get_writes() {
+                let overwritten: Vec<usize> = copies
+                    .range(..write.end)
+                    .rev()
+                    .take_while(|(_, &(end, _))| end > write.start)
+                    .map(|(&start, _)| start)
+                    .collect();
+                for start in overwritten {
+                    copies.remove(&start);
+                }
+            }
+
+            if instruction.ends_straight_line() {
+                copies.clear();
+            } else if instruction.code == COPY_INSTRUCTION_CODE {
+                let (from, to, length) = (
+                    instruction.get_operand(0),
+                    instruction.get_operand(1),
+                    instruction.get_operand(2),
+                );
+                let constant = bounded(from, length);
+                //? Static memory that nothing writes keeps its initial value
+                if from & FRAME_RELATIVE_FLAG == 0
+                    && !constant.is_empty()
+                    && writes.get_accessors(&constant).is_empty()
+                {
+                    copies.insert(to, (to.saturating_add(length), from));
+                }
+            }
+        }
+        forwarded
+    }
+
+    /// Removes copies to memory that no instruction reads, along with any copies left unread by
+    /// removing them
+    fn remove_dead_copies(&mut self) -> usize {
+        let reads = AccessIndex::new(&self.instructions, DecodedInstruction::get_reads);
+        // Number of other instructions reading the destination of each copy
+        let mut reader_counts = vec![0usize; self.instructions.len()];
+        // Copies whose destination each instruction reads
+        let mut read_copies = vec![Vec::new(); self.instructions.len()];
+        let mut dead = Vec::new();
+
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            if instruction.removed || instruction.code != COPY_INSTRUCTION_CODE {
+                continue;
+            }
+            let to = instruction.get_operand(1);
+            let mut readers =
+                reads.get_accessors(&(to..to.saturating_add(instruction.get_operand(2))));
+            readers.sort_unstable();
+            readers.dedup();
+            for reader in readers {
+                if reader != i && !self.instructions[reader].removed {
+                    reader_counts[i] += 1;
+                    read_copies[reader].push(i);
+                }
+            }
+            if reader_counts[i] == 0 {
+                dead.push(i);
+            }
+        }
+
+        //? Removing a copy only affects the copies whose destination it read
+        let mut removed = 0;
+        while let Some(i) = dead.pop() {
+            self.instructions[i].removed = true;
+            removed += 1;
+            for &copy in &read_copies[i] {
+                reader_counts[copy] -= 1;
+                if reader_counts[copy] == 0 {
+                    dead.push(copy);
+                }
+            }
+        }
+        removed
+    }
+
+    /// Points jumps to other unconditional jumps at the final destination
+    fn thread_jumps(&mut self) -> usize {
+        let mut threaded = 0;
+        for i in 0..self.instructions.len() {
+            let instruction = &self.instructions[i];
+            if instruction.removed || !instruction.is_jump() {
+                continue;
+            }
+            let original = instruction.get_destination().unwrap();
+
+            let mut destination = self.resolve(original);
+            let mut visited = HashSet::new();
+            while visited.insert(destination) {
+                let next = match self.instructions.get(self.get_index(destination)) {
+                    Some(next) if next.code == JUMP_INSTRUCTION_CODE => next,
+                    _ => break,
+                };
+                let next_destination = self.resolve(next.get_operand(0));
+                //? Leave infinite loops of jumps alone
+                if visited.contains(&next_destination) {
+                    break;
+                }
+                destination = next_destination;
+            }
+
+            if destination != self.resolve(original) {
+                let operand = self.instructions[i].get_destination_operand().unwrap();
+                self.instructions[i].set_operand(operand, destination);
+                threaded += 1;
+            }
+        }
+        threaded
+    }
+
+    /// Removes jumps to the instruction that would be executed next anyway
+    fn remove_redundant_jumps(&mut self) -> usize {
+        let mut removed = 0;
+        //? Removing a jump may make the jump before it redundant, so go backwards
+        for i in (0..self.instructions.len()).rev() {
+            let instruction = &self.instructions[i];
+            if instruction.removed || !instruction.is_jump() {
+                continue;
+            }
+            let next = self.resolve(instruction.address + instruction.get_size());
+            if self.resolve(instruction.get_destination().unwrap()) == next {
+                self.instructions[i].removed = true;
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Gets the new address of each instruction, with an extra entry for the end of the program.
+    /// Removed instructions get the address of the next remaining instruction
+    fn get_new_addresses(&self) -> Vec<usize> {
+        let mut new_addresses = Vec::with_capacity(self.instructions.len() + 1);
+        let mut address = 0;
+        for instruction in &self.instructions {
+            new_addresses.push(address);
+            if !instruction.removed {
+                address += instruction.get_size();
+            }
+        }
+        new_addresses.push(address);
+        new_addresses
+    }
+}
+
+/// Optimises program memory in place, moving any jumps, calls and debug info to the new
+/// addresses of the instructions
+///
+/// Changes reads of copies of constants to read the constant, removes copies to the same address
+/// and copies to memory that is never read, redirects jumps to jumps to the final destination,
+/// removes jumps to the next instruction and replaces an `Invert` followed by a `JumpIfNot` on
+/// the result with a `JumpIf`. Programs that jump to addresses held
+/// in variable memory are left unchanged as they can't be relocated
+pub fn optimise(
+    memory_managers: &mut MemoryManagers,
+    debug_info: Option<&mut DebugInfo>,
+) -> OptimisationReport {
+    let program = &memory_managers.program_memory.memory;
+    let mut report = OptimisationReport {
+        original_size: program.len(),
+        optimised_size: program.len(),
+        ..Default::default()
+    };
+
+    let instructions = match decode(program) {
+        Some(instructions) => instructions,
+        None => return report,
+    };
+    let mut optimiser = Optimiser {
+        instructions,
+        end: program.len(),
+    };
+    if !optimiser.has_valid_destinations()
+        || optimiser
+            .instructions
+            .iter()
+            .any(|instruction| instruction.code == JUMP_VARIABLE_INSTRUCTION_CODE)
+    {
+        return report;
+    }
+
+    report.copies_removed = optimiser.remove_self_copies();
+    report.inversions_folded = optimiser.fold_inversions();
+    report.reads_forwarded = optimiser.forward_constant_copies();
+    report.copies_removed += optimiser.remove_dead_copies();
+    report.jumps_threaded = optimiser.thread_jumps();
+    report.jumps_removed = optimiser.remove_redundant_jumps();
+
+    let new_addresses = optimiser.get_new_addresses();
+    let relocate = |address: usize| new_addresses[optimiser.get_index(address)];
+
+    let mut program = Vec::with_capacity(report.original_size);
+    for instruction in &optimiser.instructions {
+        if instruction.removed {
+            continue;
+        }
+        program.extend(instruction.code.to_le_bytes());
+        let start = program.len();
+        program.extend(&instruction.data);
+        if let Some(operand) = instruction.get_destination_operand() {
+            let destination = relocate(instruction.get_operand(operand));
+            let position = start + operand * POINTER_SIZE;
+            program[position..position + POINTER_SIZE].copy_from_slice(&encode_usize(destination));
+        }
+    }
+    report.optimised_size = program.len();
+    memory_managers.program_memory.memory = program;
+
+    if let Some(debug_info) = debug_info {
+        let mut lines = Vec::with_capacity(debug_info.lines.len());
+        for mut line in debug_info.lines.drain(..) {
+            line.address = relocate(line.address);
+            //? Lines whose instructions were all removed no longer start anywhere
+            if lines
+                .last()
+                .is_some_and(|l: &LineEntry| l.address == line.address)
+            {
+                lines.pop();
+            }
+            lines.push(line);
+        }
+        debug_info.lines = lines;
+
+        for variable in debug_info.variables.iter_mut() {
+            variable.scope_start = relocate(variable.scope_start);
+            variable.scope_end = relocate(variable.scope_end);
+        }
+    }
+
+    report
+}
@@ -1,5 +1,8 @@
+use crate::errors::CompileError;
 pub mod function_block;
 pub mod if_block;
+pub mod loop_block;
+pub mod struct_block;
 pub mod while_block;
 
 use crate::processing::processor::MemoryManagers;
@@ -14,7 +17,7 @@ pub trait BlockHandler {
         memory_managers: &mut MemoryManagers,
         block_coordinator: &mut ReferenceStack,
         symbol_line: &[Symbol],
-    ) -> Result<(), String>;
+    ) -> Result<(), CompileError>;
 
     /// Try to exit block
     ///
@@ -24,7 +27,7 @@ pub trait BlockHandler {
         memory_managers: &mut MemoryManagers,
         reference_stack: &mut ReferenceStack,
         _symbol_line: &[Symbol],
-    ) -> Result<bool, String> {
+    ) -> Result<bool, CompileError> {
         self.on_forced_exit(memory_managers, reference_stack)?;
         Ok(true)
     }
@@ -34,15 +37,37 @@ pub trait BlockHandler {
         &mut self,
         memory_managers: &mut MemoryManagers,
         block_coordinator: &mut ReferenceStack,
-    ) -> Result<(), String>;
+    ) -> Result<(), CompileError>;
 
     /// Break from block e.g. while
-    fn on_break(&mut self, _memory_managers: &mut MemoryManagers) -> Result<bool, String> {
+    fn on_break(&mut self, _memory_managers: &mut MemoryManagers) -> Result<bool, CompileError> {
         Ok(false)
     }
 
     /// Continue block e.g. while
-    fn on_continue(&mut self, _memory_managers: &mut MemoryManagers) -> Result<bool, String> {
+    fn on_continue(&mut self, _memory_managers: &mut MemoryManagers) -> Result<bool, CompileError> {
+        Ok(false)
+    }
+
+    /// Processes a line inside the block instead of it being processed as code e.g. struct fields
+    ///
+    /// Returns `Ok(true)` if the line was processed
+    fn on_line(
+        &mut self,
+        _memory_managers: &mut MemoryManagers,
+        _reference_stack: &mut ReferenceStack,
+        _symbol_line: &[Symbol],
+    ) -> Result<bool, CompileError> {
+        Ok(false)
+    }
+
+    /// Return from block e.g. function. `value` is the (possibly empty) section being returned
+    fn on_return(
+        &mut self,
+        _memory_managers: &mut MemoryManagers,
+        _reference_stack: &ReferenceStack,
+        _value: &[Symbol],
+    ) -> Result<bool, CompileError> {
         Ok(false)
     }
 }
@@ -70,18 +95,23 @@ impl BlockCoordinator {
         mut handler: Box<dyn BlockHandler>,
         memory_managers: &mut MemoryManagers,
         symbol_line: &[Symbol],
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         self.reference_stack.add_handler();
         let r = handler.on_entry(memory_managers, self.get_reference_stack_mut(), symbol_line);
+        //? Don't enter a block that failed to start
+        if r.is_err() {
+            self.reference_stack.remove_handler();
+            return r;
+        }
         self.stack.push(handler);
-        r
+        Ok(())
     }
 
     /// Break from block e.g. while
     pub fn break_block_handler(
         &mut self,
         memory_managers: &mut MemoryManagers,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         let mut success = false;
         for h in self.stack.iter_mut().rev() {
             if h.on_break(memory_managers)? {
@@ -91,7 +121,9 @@ impl BlockCoordinator {
         }
 
         if !success {
-            return Err("None of the scopes 'break' is in support breaking".to_string());
+            return Err(CompileError::InvalidControlFlow(
+                "None of the scopes 'break' is in support breaking".to_string(),
+            ));
         }
         Ok(())
     }
@@ -100,7 +132,7 @@ impl BlockCoordinator {
     pub fn continue_block_handler(
         &mut self,
         memory_managers: &mut MemoryManagers,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         let mut success = false;
         for h in self.stack.iter_mut().rev() {
             if h.on_continue(memory_managers)? {
@@ -110,7 +142,47 @@ impl BlockCoordinator {
         }
 
         if !success {
-            return Err("None of the scopes 'continue' is in support continuing".to_string());
+            return Err(CompileError::InvalidControlFlow(
+                "None of the scopes 'continue' is in support continuing".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Lets the innermost block process a line e.g. struct fields
+    ///
+    /// Returns `Ok(true)` if the line was processed
+    pub fn line_block_handler(
+        &mut self,
+        memory_managers: &mut MemoryManagers,
+        symbol_line: &[Symbol],
+    ) -> Result<bool, CompileError> {
+        match self.stack.last_mut() {
+            None => Ok(false),
+            Some(handler) => {
+                handler.on_line(memory_managers, &mut self.reference_stack, symbol_line)
+            }
+        }
+    }
+
+    /// Return from block e.g. function
+    pub fn return_block_handler(
+        &mut self,
+        memory_managers: &mut MemoryManagers,
+        value: &[Symbol],
+    ) -> Result<(), CompileError> {
+        let mut success = false;
+        for h in self.stack.iter_mut().rev() {
+            if h.on_return(memory_managers, &self.reference_stack, value)? {
+                success = true;
+                break;
+            }
+        }
+
+        if !success {
+            return Err(CompileError::InvalidControlFlow(
+                "'return' can only be used inside a function".to_string(),
+            ));
         }
         Ok(())
     }
@@ -122,7 +194,7 @@ impl BlockCoordinator {
         &mut self,
         memory_managers: &mut MemoryManagers,
         symbol_line: &[Symbol],
-    ) -> Result<bool, String> {
+    ) -> Result<bool, CompileError> {
         if self.stack.is_empty() {
             panic!("Called on_exit when not BlockHandler exists on stack!")
         }
@@ -147,7 +219,7 @@ impl BlockCoordinator {
     pub fn force_exit_block_handler(
         &mut self,
         memory_managers: &mut MemoryManagers,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         if self.stack.is_empty() {
             panic!("Called on_exit when not BlockHandler exists on stack!")
         }
@@ -175,12 +247,12 @@ impl BlockCoordinator {
     }
 
     /// Registers a variable
-    pub fn register_variable(&mut self, variable: Type, name: String) -> Result<(), String> {
+    pub fn register_variable(&mut self, variable: Type, name: String) -> Result<(), CompileError> {
         self.reference_stack.register_variable(variable, name)
     }
 
     /// Searches for a variable going up the reference stack
-    pub fn get_variable(&self, name: &str) -> Result<&Type, String> {
+    pub fn get_variable(&self, name: &str) -> Result<&Type, CompileError> {
         self.reference_stack.get_variable(name)
     }
 
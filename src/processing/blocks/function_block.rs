@@ -1,24 +1,31 @@
+use crate::errors::CompileError;
+use crate::memory_manager::FRAME_HEADER_SIZE;
 use crate::processing::blocks::BlockHandler;
 use crate::processing::instructions::jump_instruction_3::JumpInstruction;
-use crate::processing::instructions::jump_variable_instruction_4::JumpVariableInstruction;
+use crate::processing::instructions::reserve_frame_instruction_24::ReserveFrameInstruction;
+use crate::processing::instructions::return_instruction_23::ReturnInstruction;
+use crate::processing::lines::arithmetic::handle_arithmetic_section;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::reference_manager::ReferenceStack;
 use crate::processing::symbols::{try_arithmetic_block_into_parameters, Literal, Symbol};
 use crate::processing::types::function::FunctionType;
-use crate::processing::types::{get_type, Type};
-use crate::util::warn;
-use std::mem::size_of;
+use crate::processing::types::{get_scoped_type, get_static_type, Type};
 
 pub struct FunctionBlock {
-    jump_variable: Option<usize>,
     skip_instruction: Option<JumpInstruction>,
+    return_instructions: Vec<JumpInstruction>,
+    reserve_frame_instruction: Option<ReserveFrameInstruction>,
+    /// Static slot the return value is passed out of the stack frame through
+    static_return: Option<Type>,
 }
 
 impl FunctionBlock {
     pub fn new_block() -> Box<dyn BlockHandler> {
         Box::new(Self {
-            jump_variable: None,
             skip_instruction: None,
+            return_instructions: Vec::new(),
+            reserve_frame_instruction: None,
+            static_return: None,
         })
     }
 }
@@ -29,12 +36,12 @@ impl BlockHandler for FunctionBlock {
         memory_managers: &mut MemoryManagers,
         reference_stack: &mut ReferenceStack,
         symbol_line: &[Symbol],
-    ) -> Result<(), String> {
-        warn("Functions do not currently support recursion (including indirect). Usage will create unstable behaviour!");
-
-        fn formatting_error() -> String {
-            "Function declaration must be formatted 'fn [FUNCTION NAME] ([PARAMETER LIST]) {OPTIONAL [RETURN TYPE] [DEFAULT RETURN VALUE]}'"
-                .to_string()
+    ) -> Result<(), CompileError> {
+        fn formatting_error() -> CompileError {
+            CompileError::Syntax(
+                "Function declaration must be formatted 'fn [FUNCTION NAME] ([PARAMETER LIST]) {OPTIONAL [RETURN TYPE] [DEFAULT RETURN VALUE]}'"
+                    .to_string(),
+            )
         }
 
         //? Insert skip instruction
@@ -46,13 +53,13 @@ impl BlockHandler for FunctionBlock {
         }
 
         let name = match &symbol_line[1] {
-            Symbol::Name(name) => name.clone(),
+            Symbol::Name(name, _) => name.clone(),
             _ => return Err(formatting_error()),
         };
 
         let parameters = match &symbol_line[2] {
-            Symbol::ArithmeticBlock(_) => {
-                match try_arithmetic_block_into_parameters(&symbol_line[2])? {
+            Symbol::ArithmeticBlock(..) => {
+                match try_arithmetic_block_into_parameters(&symbol_line[2], reference_stack)? {
                     Literal::ParameterList(list) => list,
                     _ => panic!(),
                 }
@@ -61,67 +68,117 @@ impl BlockHandler for FunctionBlock {
         };
 
         let return_type;
+        let default_return_value;
         if symbol_line.len() == 3 {
             return_type = None;
+            default_return_value = None;
         } else {
-            return_type = match &symbol_line[3] {
-                Symbol::Type(type_symbol) => Some(get_type(type_symbol, memory_managers)?),
-                _ => return Err(formatting_error()),
+            return_type = match reference_stack.get_type_symbol(&symbol_line[3]) {
+                Some(type_symbol) => Some(type_symbol),
+                None => return Err(formatting_error()),
             };
 
-            let default_return_value = match &symbol_line[4] {
-                Symbol::Literal(literal) => literal,
+            default_return_value = match &symbol_line[4] {
+                Symbol::Literal(literal, _) => Some(literal),
                 _ => return Err(formatting_error()),
             };
-
-            return_type
-                .as_ref()
-                .unwrap()
-                .static_assign_literal(memory_managers, default_return_value)?;
         }
 
-        //? Register and clone parameters
-        let mut to_assign = Vec::new();
-
-        for (type_symbol, name) in parameters {
-            let created_type = get_type(&type_symbol, memory_managers)?;
-            to_assign.push(created_type.duplicate());
-            reference_stack.register_variable(created_type, name)?;
+        //? Create static slots to pass values into and out of the function
+        let mut static_parameters = Vec::new();
+        for (type_symbol, _, _) in &parameters {
+            static_parameters.push(get_static_type(
+                type_symbol,
+                memory_managers,
+                reference_stack,
+            )?);
         }
 
-        if return_type.is_some() {
-            reference_stack.register_variable(
-                return_type.as_ref().unwrap().duplicate(),
-                "return".to_string(),
-            )?;
-        }
-
-        self.jump_variable = Some(memory_managers.variable_memory.reserve(size_of::<usize>()));
+        let static_return = match &return_type {
+            None => None,
+            Some(type_symbol) => Some(get_static_type(
+                type_symbol,
+                memory_managers,
+                reference_stack,
+            )?),
+        };
 
-        //? Register function in above handler
-        let function = FunctionType::create_empty(
-            to_assign,
-            return_type,
-            memory_managers.program_memory.get_position(),
-            self.jump_variable.unwrap(),
-        );
+        //? Start stack frame
+        let start_address = memory_managers.program_memory.get_position();
+        memory_managers.variable_memory.enter_frame();
+        self.reserve_frame_instruction =
+            Some(ReserveFrameInstruction::new_alloc(memory_managers, 0));
+
+        let set_up_frame = || -> Result<(), CompileError> {
+            //? Copy parameters into the stack frame and register them
+            for ((type_symbol, name, span), static_parameter) in
+                parameters.into_iter().zip(static_parameters.iter())
+            {
+                let created_type = get_scoped_type(&type_symbol, memory_managers, reference_stack)?;
+                created_type.assign_clone(memory_managers, static_parameter)?;
+                reference_stack
+                    .register_variable(created_type, name)
+                    .map_err(|e| e.with_span(span))?;
+            }
 
-        reference_stack.register_variable_with_offset(
-            Type::new(Box::new(function), memory_managers),
-            name,
-            1,
-        )?;
+            if let Some(type_symbol) = &return_type {
+                let frame_return = get_scoped_type(type_symbol, memory_managers, reference_stack)?;
+                frame_return
+                    .static_assign_literal(memory_managers, default_return_value.unwrap())?;
+                reference_stack.register_variable(frame_return, "return".to_string())?;
+            }
 
-        Ok(())
+            //? Register function in above handler
+            let function = FunctionType::create_empty(
+                static_parameters,
+                static_return.as_ref().map(|r| r.duplicate()),
+                start_address,
+            );
+            self.static_return = static_return;
+
+            reference_stack.register_variable_with_offset(
+                Type::new(Box::new(function), memory_managers),
+                name,
+                1,
+            )?;
+            reference_stack.set_function_scope();
+            Ok(())
+        };
+        let result = set_up_frame();
+        //? Later variables mustn't be allocated in the frame of a function that failed to start
+        if result.is_err() {
+            memory_managers.variable_memory.exit_frame();
+        }
+        result
     }
 
     fn on_forced_exit(
         &mut self,
         memory_managers: &mut MemoryManagers,
-        _reference_stack: &mut ReferenceStack,
-    ) -> Result<(), String> {
+        reference_stack: &mut ReferenceStack,
+    ) -> Result<(), CompileError> {
+        //? Make early returns jump to the end of the function
+        for i in self.return_instructions.iter_mut() {
+            i.set_destination(
+                memory_managers,
+                memory_managers.program_memory.get_position(),
+            );
+        }
+
+        //? Copy return value out of the stack frame
+        if let Some(static_return) = &self.static_return {
+            let frame_return = reference_stack.get_variable("return")?;
+            static_return.assign_clone(memory_managers, frame_return)?;
+        }
+
         //? Insert instruction to jump back to calling location
-        JumpVariableInstruction::new_alloc(memory_managers, self.jump_variable.unwrap());
+        ReturnInstruction::new_alloc(memory_managers);
+
+        let frame_size = memory_managers.variable_memory.exit_frame();
+        self.reserve_frame_instruction
+            .as_ref()
+            .unwrap()
+            .set_size(memory_managers, frame_size - FRAME_HEADER_SIZE);
 
         self.skip_instruction.as_mut().unwrap().set_destination(
             memory_managers,
@@ -130,4 +187,44 @@ impl BlockHandler for FunctionBlock {
 
         Ok(())
     }
+
+    fn on_return(
+        &mut self,
+        memory_managers: &mut MemoryManagers,
+        reference_stack: &ReferenceStack,
+        value: &[Symbol],
+    ) -> Result<bool, CompileError> {
+        //? Evaluate value into return variable
+        if !value.is_empty() {
+            if self.static_return.is_none() {
+                return Err(CompileError::NoReturnValue);
+            }
+
+            let frame_return = reference_stack.get_variable("return")?;
+            handle_arithmetic_section(
+                memory_managers,
+                reference_stack,
+                value,
+                Some(frame_return),
+                true,
+            )?;
+        }
+
+        // Go to end of function
+        self.return_instructions
+            .push(JumpInstruction::new_alloc(memory_managers, 0));
+        Ok(true)
+    }
+
+    fn on_break(&mut self, _memory_managers: &mut MemoryManagers) -> Result<bool, CompileError> {
+        Err(CompileError::InvalidControlFlow(
+            "Cannot break out of a function".to_string(),
+        ))
+    }
+
+    fn on_continue(&mut self, _memory_managers: &mut MemoryManagers) -> Result<bool, CompileError> {
+        Err(CompileError::InvalidControlFlow(
+            "Cannot continue out of a function".to_string(),
+        ))
+    }
 }
@@ -1,3 +1,4 @@
+use crate::errors::CompileError;
 use crate::processing::blocks::BlockHandler;
 use crate::processing::instructions::jump_if_not_instruction_2::JumpIfNotInstruction;
 use crate::processing::instructions::jump_instruction_3::JumpInstruction;
@@ -27,7 +28,7 @@ impl BlockHandler for IfBlock {
         memory_managers: &mut MemoryManagers,
         reference_stack: &mut ReferenceStack,
         symbol_line: &[Symbol],
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         //? Extract condition boolean
 
         let condition_boolean = match handle_arithmetic_section(
@@ -40,17 +41,17 @@ impl BlockHandler for IfBlock {
             Err(e) => return Err(e),
             Ok(value) => {
                 if value.is_none() {
-                    return Err("Section does not evaluate to a value".to_string());
+                    return Err(CompileError::NoValue);
                 }
                 value.unwrap()
             }
         };
 
         if condition_boolean.get_type() != TypeSymbol::Boolean {
-            return Err(format!(
-                "If expression must evaluate to {:?}",
-                TypeSymbol::Boolean
-            ));
+            return Err(CompileError::TypeMismatch {
+                expected: TypeSymbol::Boolean,
+                found: condition_boolean.get_type(),
+            });
         }
 
         //? Insert instruction to skip this section if boolean is false
@@ -68,12 +69,12 @@ impl BlockHandler for IfBlock {
         memory_managers: &mut MemoryManagers,
         reference_stack: &mut ReferenceStack,
         symbol_line: &[Symbol],
-    ) -> Result<bool, String> {
+    ) -> Result<bool, CompileError> {
         fn exit_with_cleanup(
             this: &mut IfBlock,
             memory_managers: &mut MemoryManagers,
             reference_stack: &mut ReferenceStack,
-        ) -> Result<bool, String> {
+        ) -> Result<bool, CompileError> {
             this.on_forced_exit(memory_managers, reference_stack)?;
             Ok(true)
         }
@@ -95,10 +96,10 @@ impl BlockHandler for IfBlock {
         match block_type {
             Block::Elif => {
                 if self.jump_next_instruction.is_none() {
-                    return Err(
+                    return Err(CompileError::InvalidControlFlow(
                         "'elif' cannot follow an 'else' block as it will never be reached"
                             .to_string(),
-                    );
+                    ));
                 }
 
                 // Add instruction to skip to end if previous if/elif condition was met and executed
@@ -121,13 +122,15 @@ impl BlockHandler for IfBlock {
             }
             Block::Else => {
                 if symbol_line.len() > 1 {
-                    return Err("Else cannot be followed by any other symbol".to_string());
+                    return Err(CompileError::Syntax(
+                        "Else cannot be followed by any other symbol".to_string(),
+                    ));
                 }
                 if self.jump_next_instruction.is_none() {
-                    return Err(
+                    return Err(CompileError::InvalidControlFlow(
                         "'else' cannot follow an 'else' block as it will never be reached"
                             .to_string(),
-                    );
+                    ));
                 }
                 // Add instruction to skip to end if previous if/elif condition was met and executed
                 self.jump_end_instructions
@@ -155,7 +158,7 @@ impl BlockHandler for IfBlock {
         &mut self,
         memory_managers: &mut MemoryManagers,
         _reference_stack: &mut ReferenceStack,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         /*
         If :: Jump to next if not
             content
@@ -0,0 +1,77 @@
+use crate::errors::CompileError;
+use crate::processing::blocks::BlockHandler;
+
+use crate::processing::instructions::jump_instruction_3::JumpInstruction;
+use crate::processing::processor::MemoryManagers;
+use crate::processing::reference_manager::ReferenceStack;
+use crate::processing::symbols::Symbol;
+
+pub struct LoopBlock {
+    jump_end_instructions: Vec<JumpInstruction>,
+    jump_start_instructions: Vec<JumpInstruction>,
+    start_position: Option<usize>,
+}
+
+impl LoopBlock {
+    pub fn new_block() -> Box<dyn BlockHandler> {
+        Box::new(Self {
+            jump_end_instructions: Vec::new(),
+            jump_start_instructions: Vec::new(),
+            start_position: None,
+        })
+    }
+}
+
+impl BlockHandler for LoopBlock {
+    fn on_entry(
+        &mut self,
+        memory_managers: &mut MemoryManagers,
+        _reference_stack: &mut ReferenceStack,
+        symbol_line: &[Symbol],
+    ) -> Result<(), CompileError> {
+        if symbol_line.len() != 1 {
+            return Err(CompileError::Syntax(
+                "Loop must be formatted 'loop' with nothing following it".to_string(),
+            ));
+        }
+
+        self.start_position = Some(memory_managers.program_memory.get_position());
+
+        Ok(())
+    }
+
+    fn on_forced_exit(
+        &mut self,
+        memory_managers: &mut MemoryManagers,
+        _reference_stack: &mut ReferenceStack,
+    ) -> Result<(), CompileError> {
+        //? Insert looping instruction
+        JumpInstruction::new_alloc(memory_managers, self.start_position.unwrap());
+
+        //? Set all instructions to jump to correct locations
+        for i in self.jump_end_instructions.iter_mut() {
+            i.set_destination(
+                memory_managers,
+                memory_managers.program_memory.get_position(),
+            );
+        }
+        for i in self.jump_start_instructions.iter_mut() {
+            i.set_destination(memory_managers, self.start_position.unwrap());
+        }
+        Ok(())
+    }
+
+    fn on_break(&mut self, memory_managers: &mut MemoryManagers) -> Result<bool, CompileError> {
+        // Go to end of loop
+        self.jump_end_instructions
+            .push(JumpInstruction::new_alloc(memory_managers, 0));
+        Ok(true)
+    }
+
+    fn on_continue(&mut self, memory_managers: &mut MemoryManagers) -> Result<bool, CompileError> {
+        // Go to start of loop
+        self.jump_start_instructions
+            .push(JumpInstruction::new_alloc(memory_managers, 0));
+        Ok(true)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::errors::CompileError;
+use crate::processing::blocks::BlockHandler;
+use crate::processing::processor::MemoryManagers;
+use crate::processing::reference_manager::ReferenceStack;
+use crate::processing::symbols::{Literal, Symbol};
+use crate::processing::types::structure::{StructDefinition, StructField};
+
+pub struct StructBlock {
+    name: Option<String>,
+    fields: Vec<StructField>,
+}
+
+impl StructBlock {
+    pub fn new_block() -> Box<dyn BlockHandler> {
+        Box::new(Self {
+            name: None,
+            fields: Vec::new(),
+        })
+    }
+}
+
+impl BlockHandler for StructBlock {
+    fn on_entry(
+        &mut self,
+        _memory_managers: &mut MemoryManagers,
+        _reference_stack: &mut ReferenceStack,
+        symbol_line: &[Symbol],
+    ) -> Result<(), CompileError> {
+        let name = match symbol_line {
+            [_, Symbol::Name(name, _)] if !name.contains('.') => name,
+            _ => {
+                return Err(CompileError::Syntax(
+                    "Struct declaration must be formatted 'struct [STRUCT NAME]'".to_string(),
+                ))
+            }
+        };
+
+        self.name = Some(name.clone());
+        Ok(())
+    }
+
+    fn on_line(
+        &mut self,
+        _memory_managers: &mut MemoryManagers,
+        reference_stack: &mut ReferenceStack,
+        symbol_line: &[Symbol],
+    ) -> Result<bool, CompileError> {
+        fn formatting_error() -> CompileError {
+            CompileError::Syntax(
+                "Struct fields must be formatted '[TYPE] [FIELD NAME]' or '[TYPE][[COUNT]] [FIELD NAME]'"
+                    .to_string(),
+            )
+        }
+
+        //? Extract type, count and name
+        let (type_symbol, count, name, span) = match symbol_line {
+            [type_symbol, Symbol::Name(name, span)] => (type_symbol, None, name, *span),
+            [type_symbol, Symbol::Indexer(count, count_span), Symbol::Name(name, span)] => {
+                let count = match count.as_ref() {
+                    Symbol::Literal(Literal::Int(count), _) if *count > 0 => *count as usize,
+                    _ => {
+                        return Err(CompileError::InvalidLiteral(
+                            "Field count must be a positive integer".to_string(),
+                        )
+                        .with_span(*count_span))
+                    }
+                };
+                (type_symbol, Some(count), name, *span)
+            }
+            _ => return Err(formatting_error()),
+        };
+
+        let type_symbol = match reference_stack.get_type_symbol(type_symbol) {
+            Some(type_symbol) => type_symbol,
+            None => return Err(formatting_error()),
+        };
+
+        if name.contains('.') {
+            return Err(formatting_error());
+        }
+
+        if self.fields.iter().any(|f| f.name == *name) {
+            return Err(CompileError::DuplicateField(name.clone()).with_span(span));
+        }
+
+        self.fields.push(StructField {
+            name: name.clone(),
+            type_symbol,
+            count,
+        });
+
+        Ok(true)
+    }
+
+    fn on_forced_exit(
+        &mut self,
+        _memory_managers: &mut MemoryManagers,
+        reference_stack: &mut ReferenceStack,
+    ) -> Result<(), CompileError> {
+        let name = self.name.take().unwrap();
+
+        if self.fields.is_empty() {
+            return Err(CompileError::Syntax(format!(
+                "Struct '{}' must have at least one field",
+                name
+            )));
+        }
+
+        //? Register struct in above handler
+        let definition =
+            StructDefinition::new(name, std::mem::take(&mut self.fields), reference_stack)?;
+        reference_stack.register_struct_with_offset(definition, 1)
+    }
+}
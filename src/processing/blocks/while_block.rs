@@ -1,3 +1,4 @@
+use crate::errors::CompileError;
 use crate::processing::blocks::BlockHandler;
 
 use crate::processing::instructions::jump_if_not_instruction_2::JumpIfNotInstruction;
@@ -32,7 +33,7 @@ impl BlockHandler for WhileBlock {
         memory_managers: &mut MemoryManagers,
         reference_stack: &mut ReferenceStack,
         symbol_line: &[Symbol],
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         // Save position before boolean evaluation
         self.start_position = Some(memory_managers.program_memory.get_position());
 
@@ -47,17 +48,17 @@ impl BlockHandler for WhileBlock {
             Err(e) => return Err(e),
             Ok(value) => {
                 if value.is_none() {
-                    return Err("Section does not evaluate to a value".to_string());
+                    return Err(CompileError::NoValue);
                 }
                 value.unwrap()
             }
         };
 
         if condition_boolean.get_type() != TypeSymbol::Boolean {
-            return Err(format!(
-                "If expression must evaluate to {:?}",
-                TypeSymbol::Boolean
-            ));
+            return Err(CompileError::TypeMismatch {
+                expected: TypeSymbol::Boolean,
+                found: condition_boolean.get_type(),
+            });
         }
 
         //? Create instruction to leave while if condition is false
@@ -75,7 +76,7 @@ impl BlockHandler for WhileBlock {
         memory_managers: &mut MemoryManagers,
         reference_stack: &mut ReferenceStack,
         _symbol_line: &[Symbol],
-    ) -> Result<bool, String> {
+    ) -> Result<bool, CompileError> {
         self.on_forced_exit(memory_managers, reference_stack)?;
         Ok(true)
     }
@@ -84,7 +85,7 @@ impl BlockHandler for WhileBlock {
         &mut self,
         memory_managers: &mut MemoryManagers,
         _reference_stack: &mut ReferenceStack,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         //? Insert looping instruction
         JumpInstruction::new_alloc(memory_managers, self.start_position.unwrap());
 
@@ -105,14 +106,14 @@ impl BlockHandler for WhileBlock {
         Ok(())
     }
 
-    fn on_break(&mut self, memory_managers: &mut MemoryManagers) -> Result<bool, String> {
+    fn on_break(&mut self, memory_managers: &mut MemoryManagers) -> Result<bool, CompileError> {
         // Go to end of while
         self.jump_end_instructions
             .push(JumpInstruction::new_alloc(memory_managers, 0));
         Ok(true)
     }
 
-    fn on_continue(&mut self, memory_managers: &mut MemoryManagers) -> Result<bool, String> {
+    fn on_continue(&mut self, memory_managers: &mut MemoryManagers) -> Result<bool, CompileError> {
         // Go to start of while
         self.jump_start_instructions
             .push(JumpInstruction::new_alloc(memory_managers, 0));
@@ -1,19 +1,32 @@
 pub mod add_instruction_13;
 pub mod and_instruction_6;
+pub mod call_instruction_22;
+pub mod concat_instruction_25;
 pub mod copy_instruction_0;
+pub mod divide_instruction_18;
 pub mod dynamic_from_copy_instruction_10;
 pub mod dynamic_to_copy_instruction_11;
 pub mod equal_instruction_7;
+pub mod greater_equal_instruction_21;
+pub mod greater_instruction_20;
 pub mod input_instruction_15;
 pub mod invert_instruction_1;
 pub mod jump_if_instruction_12;
 pub mod jump_if_not_instruction_2;
 pub mod jump_instruction_3;
 pub mod jump_variable_instruction_4;
+pub mod modulo_instruction_19;
+pub mod multiply_instruction_17;
 pub mod not_equal_instruction_14;
 pub mod or_instruction_8;
 pub mod print_chars_instruction_9;
 pub mod print_instruction_5;
+pub mod print_string_instruction_28;
+pub mod reserve_frame_instruction_24;
+pub mod return_instruction_23;
+pub mod slice_instruction_27;
+pub mod string_index_instruction_26;
+pub mod subtract_instruction_16;
 
 pub const INSTRUCTION_CODE_LENGTH: usize = 2;
 
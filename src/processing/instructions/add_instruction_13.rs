@@ -1,7 +1,7 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::processor::MemoryManagers;
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct AddInstruction {
     address: usize,
@@ -19,10 +19,10 @@ impl AddInstruction {
     ) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(ADD_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(lhs.to_le_bytes());
-        instruction_memory.extend(rhs.to_le_bytes());
-        instruction_memory.extend(len.to_le_bytes());
-        instruction_memory.extend(dest.to_le_bytes());
+        instruction_memory.extend(encode_usize(lhs));
+        instruction_memory.extend(encode_usize(rhs));
+        instruction_memory.extend(encode_usize(len));
+        instruction_memory.extend(encode_usize(dest));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -36,45 +36,41 @@ impl AddInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 4
+        POINTER_SIZE * 4
     }
 
     pub fn get_debug(data: &[u8]) -> String {
         format!(
             "ADD [{} to {}] (len: {}) dest [{}]",
-            get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
-            get_usize(&(size_of::<usize>() * 2), data),
-            get_usize(&(size_of::<usize>() * 3), data),
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            get_usize(&(POINTER_SIZE * 2), data),
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let lhs = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let rhs = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let len = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let dest = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-
-        let mut overflow = false;
-
-        for i in 0..len {
-            let a = memory_managers.variable_memory.memory[lhs + i];
-            let b = memory_managers.variable_memory.memory[rhs + i];
-
-            let result = if !overflow {
-                a.wrapping_add(b)
-            } else {
-                a.wrapping_add(b).wrapping_add(1)
-            }; // Carry
-
-            overflow = result < a || result < b;
-
-            memory_managers.variable_memory.memory[dest + i] = result;
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let lhs = memory_managers.read_variable_address(pointer)?;
+        let rhs = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+
+        let memory = &memory_managers.variable_memory;
+        let lhs = memory.read(lhs, len)?;
+        let rhs = memory.read(rhs, len)?;
+        let mut result = Vec::with_capacity(len);
+        let mut carry: u16 = 0;
+        for (a, b) in lhs.iter().zip(rhs) {
+            let sum = *a as u16 + *b as u16 + carry;
+            carry = sum >> 8;
+
+            result.push(sum as u8);
         }
+
+        memory_managers.variable_memory.write(dest, &result)
     }
 }
 
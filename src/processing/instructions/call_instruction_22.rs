@@ -0,0 +1,54 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::processor::MemoryManagers;
+use crate::util::{encode_usize, get_usize, POINTER_SIZE};
+
+pub struct CallInstruction {
+    address: usize,
+}
+
+pub const CALL_INSTRUCTION_CODE: u16 = 22;
+
+/// Pushes a new stack frame and jumps to a function
+impl CallInstruction {
+    pub fn new_alloc(memory_managers: &mut MemoryManagers, dest: usize) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(CALL_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(dest));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        CALL_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE // dest
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!("CALL [{}]", get_usize(&0, data))
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let dest = memory_managers.read_operand(pointer)?;
+
+        memory_managers.variable_memory.push_frame(*pointer)?;
+        *pointer = dest;
+        Ok(())
+    }
+}
+
+impl Instruction for CallInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
@@ -0,0 +1,93 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
+use crate::processing::processor::MemoryManagers;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
+
+pub struct ConcatInstruction {
+    address: usize,
+}
+
+pub const CONCAT_INSTRUCTION_CODE: u16 = 25;
+
+/// Joins two strings. Strings are stored as a length (`usize`) followed by `capacity` bytes
+impl ConcatInstruction {
+    pub fn new_alloc(
+        memory_managers: &mut MemoryManagers,
+        lhs: usize,
+        rhs: usize,
+        dest: usize,
+        capacity: usize,
+    ) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(CONCAT_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(lhs));
+        instruction_memory.extend(encode_usize(rhs));
+        instruction_memory.extend(encode_usize(dest));
+        instruction_memory.extend(encode_usize(capacity));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        CONCAT_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE * 4 // LHS, RHS, dest, capacity
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!(
+            "CONCAT [{}] + [{}] dest [{}] (capacity: {})",
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            format_address(get_usize(&(POINTER_SIZE * 2), data)),
+            get_usize(&(POINTER_SIZE * 3), data),
+        )
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let instruction_address = *pointer - INSTRUCTION_CODE_LENGTH;
+        let lhs = memory_managers.read_variable_address(pointer)?;
+        let rhs = memory_managers.read_variable_address(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+        let capacity = memory_managers.read_operand(pointer)?;
+
+        let memory = &memory_managers.variable_memory;
+        let lhs_len = memory.read_usize(lhs)?;
+        let rhs_len = memory.read_usize(rhs)?;
+        let len = lhs_len.saturating_add(rhs_len);
+        if len > capacity {
+            return Err(RuntimeError::StringOverflow {
+                length: len,
+                capacity,
+                address: instruction_address,
+            });
+        }
+
+        // Build result separately as the destination may be one of the operands
+        let size = POINTER_SIZE.saturating_add(capacity);
+        memory.read(dest, size)?;
+        let mut result = Vec::with_capacity(size);
+        result.extend(encode_usize(len));
+        result.extend(memory.read(lhs + POINTER_SIZE, lhs_len)?);
+        result.extend(memory.read(rhs + POINTER_SIZE, rhs_len)?);
+        result.resize(size, 0);
+
+        memory_managers.variable_memory.write(dest, &result)
+    }
+}
+
+impl Instruction for ConcatInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
@@ -1,7 +1,7 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::processor::MemoryManagers;
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct CopyInstruction {
     address: usize,
@@ -18,9 +18,9 @@ impl CopyInstruction {
     ) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(COPY_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(from.to_le_bytes());
-        instruction_memory.extend(to.to_le_bytes());
-        instruction_memory.extend(length.to_le_bytes());
+        instruction_memory.extend(encode_usize(from));
+        instruction_memory.extend(encode_usize(to));
+        instruction_memory.extend(encode_usize(length));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -34,30 +34,27 @@ impl CopyInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 3 // From, To,  Length
+        POINTER_SIZE * 3 // From, To,  Length
     }
 
     pub(crate) fn get_debug(data: &[u8]) -> String {
         format!(
             "COPY [{}] (len:{}) dest [{}]",
-            get_usize(&0, data),
-            get_usize(&(size_of::<usize>() * 2), data),
-            get_usize(&size_of::<usize>(), data),
+            format_address(get_usize(&0, data)),
+            get_usize(&(POINTER_SIZE * 2), data),
+            format_address(get_usize(&POINTER_SIZE, data)),
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let from = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let to = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let len = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let from = memory_managers.read_variable_address(pointer)?;
+        let to = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
 
-        for i in 0..len {
-            memory_managers.variable_memory.memory[to + i] =
-                memory_managers.variable_memory.memory[from + i];
-        }
+        memory_managers.variable_memory.copy(from, to, len)
     }
 }
 
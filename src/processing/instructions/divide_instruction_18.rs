@@ -0,0 +1,174 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
+use crate::processing::processor::MemoryManagers;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
+
+pub struct DivideInstruction {
+    address: usize,
+}
+
+pub const DIVIDE_INSTRUCTION_CODE: u16 = 18;
+
+/// Negates a little-endian two's complement value in place
+fn negate(value: &mut [u8]) {
+    let mut carry = true;
+    for byte in value.iter_mut() {
+        *byte = !*byte;
+        if carry {
+            let (result, overflow) = byte.overflowing_add(1);
+            *byte = result;
+            carry = overflow;
+        }
+    }
+}
+
+/// Divides two little-endian values of equal length using binary long division
+///
+/// # Returns
+/// * `Some((quotient, remainder))` - Quotient truncated towards zero and a remainder with the sign of `lhs`
+/// * `None` - `rhs` is zero
+pub fn divide_bytes(lhs: &[u8], rhs: &[u8], signed: bool) -> Option<(Vec<u8>, Vec<u8>)> {
+    if rhs.iter().all(|b| *b == 0) {
+        return None;
+    }
+
+    let is_negative = |value: &[u8]| signed && value.last().copied().unwrap_or(0) & 0x80 != 0;
+    let lhs_negative = is_negative(lhs);
+    let rhs_negative = is_negative(rhs);
+
+    // Divide magnitudes
+    let mut dividend = lhs.to_vec();
+    let mut divisor = rhs.to_vec();
+    if lhs_negative {
+        negate(&mut dividend);
+    }
+    if rhs_negative {
+        negate(&mut divisor);
+    }
+
+    let len = lhs.len();
+    let mut quotient = vec![0u8; len];
+    let mut remainder = vec![0u8; len];
+
+    for bit in (0..(len * 8)).rev() {
+        // remainder = (remainder << 1) | next bit of dividend
+        let mut carry = (dividend[bit / 8] >> (bit % 8)) & 1;
+        for byte in remainder.iter_mut() {
+            let next_carry = *byte >> 7;
+            *byte = (*byte << 1) | carry;
+            carry = next_carry;
+        }
+
+        // Remainder can exceed the value range by one bit when dividing by a divisor with the top bit set
+        let greater_equal = carry == 1 || {
+            let mut greater_equal = true;
+            for i in (0..len).rev() {
+                if remainder[i] != divisor[i] {
+                    greater_equal = remainder[i] > divisor[i];
+                    break;
+                }
+            }
+            greater_equal
+        };
+
+        if greater_equal {
+            let mut borrow = 0i16;
+            for i in 0..len {
+                let mut result = remainder[i] as i16 - divisor[i] as i16 - borrow;
+                borrow = 0;
+                if result < 0 {
+                    result += 0x100;
+                    borrow = 1;
+                }
+                remainder[i] = result as u8;
+            }
+            quotient[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    if lhs_negative != rhs_negative {
+        negate(&mut quotient);
+    }
+    if lhs_negative {
+        negate(&mut remainder);
+    }
+
+    Some((quotient, remainder))
+}
+
+/// Divides LHS by RHS, truncating towards zero
+impl DivideInstruction {
+    pub fn new_alloc(
+        memory_managers: &mut MemoryManagers,
+        lhs: usize,
+        rhs: usize,
+        len: usize,
+        dest: usize,
+        signed: bool,
+    ) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(DIVIDE_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(lhs));
+        instruction_memory.extend(encode_usize(rhs));
+        instruction_memory.extend(encode_usize(len));
+        instruction_memory.extend(encode_usize(dest));
+        instruction_memory.extend(encode_usize(signed as usize));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        DIVIDE_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE * 5 // LHS, RHS, len, dest, signed
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!(
+            "DIVIDE [{} by {}] (len: {}) dest [{}] (signed: {})",
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            get_usize(&(POINTER_SIZE * 2), data),
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
+            get_usize(&(POINTER_SIZE * 4), data) != 0,
+        )
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let instruction_address = *pointer - INSTRUCTION_CODE_LENGTH;
+        let lhs = memory_managers.read_variable_address(pointer)?;
+        let rhs = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+        let signed = memory_managers.read_operand(pointer)? != 0;
+
+        let memory = &memory_managers.variable_memory;
+        let (quotient, _) =
+            match divide_bytes(memory.read(lhs, len)?, memory.read(rhs, len)?, signed) {
+                None => {
+                    return Err(RuntimeError::DivisionByZero {
+                        address: instruction_address,
+                    })
+                }
+                Some(value) => value,
+            };
+
+        memory_managers.variable_memory.write(dest, &quotient)
+    }
+}
+
+impl Instruction for DivideInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
@@ -1,7 +1,8 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
 use crate::processing::processor::MemoryManagers;
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct DynamicFromCopyInstruction {
     address: usize,
@@ -17,14 +18,16 @@ impl DynamicFromCopyInstruction {
         from_pointer_location: usize,
         direct_to: usize,
         length: usize,
+        array_length: usize,
     ) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(DYNAMIC_FROM_COPY_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(from_location.to_le_bytes());
-        instruction_memory.extend(indexing_size.to_le_bytes());
-        instruction_memory.extend(from_pointer_location.to_le_bytes());
-        instruction_memory.extend(direct_to.to_le_bytes());
-        instruction_memory.extend(length.to_le_bytes());
+        instruction_memory.extend(encode_usize(from_location));
+        instruction_memory.extend(encode_usize(indexing_size));
+        instruction_memory.extend(encode_usize(from_pointer_location));
+        instruction_memory.extend(encode_usize(direct_to));
+        instruction_memory.extend(encode_usize(length));
+        instruction_memory.extend(encode_usize(array_length));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -38,39 +41,48 @@ impl DynamicFromCopyInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 5
+        POINTER_SIZE * 6
     }
 
     pub(crate) fn get_debug(data: &[u8]) -> String {
         format!(
-            "DYNAMIC COPY [{}:{}:{}] (len:{}) dest [{}]",
-            get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
-            get_usize(&(size_of::<usize>() * 2), data),
-            get_usize(&(size_of::<usize>() * 4), data),
-            get_usize(&(size_of::<usize>() * 3), data),
+            "DYNAMIC COPY [{}:{}:{}] (len:{}) dest [{}] (of:{})",
+            format_address(get_usize(&0, data)),
+            get_usize(&POINTER_SIZE, data),
+            format_address(get_usize(&(POINTER_SIZE * 2), data)),
+            get_usize(&(POINTER_SIZE * 4), data),
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
+            get_usize(&(POINTER_SIZE * 5), data),
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let from_location = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let indexing_size = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let from_pointer = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let direct_to = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let length = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-
-        let actual_from = get_usize(&from_pointer, &memory_managers.variable_memory.memory);
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let instruction_address = *pointer - INSTRUCTION_CODE_LENGTH;
+        let from_location = memory_managers.read_variable_address(pointer)?;
+        let indexing_size = memory_managers.read_operand(pointer)?;
+        let from_pointer = memory_managers.read_variable_address(pointer)?;
+        let direct_to = memory_managers.read_variable_address(pointer)?;
+        let length = memory_managers.read_operand(pointer)?;
+        let array_length = memory_managers.read_operand(pointer)?;
 
-        for i in 0..length {
-            memory_managers.variable_memory.memory[direct_to + i] = memory_managers
-                .variable_memory
-                .memory[from_location + (actual_from * indexing_size) + i];
+        let actual_from = memory_managers.variable_memory.read_usize(from_pointer)?;
+        if actual_from >= array_length {
+            return Err(RuntimeError::IndexOutOfBounds {
+                index: actual_from,
+                length: array_length,
+                address: instruction_address,
+            });
         }
+
+        let element = actual_from
+            .saturating_mul(indexing_size)
+            .saturating_add(from_location);
+        memory_managers
+            .variable_memory
+            .copy(element, direct_to, length)
     }
 }
 
@@ -1,7 +1,8 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
 use crate::processing::processor::MemoryManagers;
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct DynamicToCopyInstruction {
     address: usize,
@@ -17,14 +18,16 @@ impl DynamicToCopyInstruction {
         to_pointer_location: usize,
         direct_from: usize,
         length: usize,
+        array_length: usize,
     ) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(DYNAMIC_TO_COPY_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(to_location.to_le_bytes());
-        instruction_memory.extend(indexing_size.to_le_bytes());
-        instruction_memory.extend(to_pointer_location.to_le_bytes());
-        instruction_memory.extend(direct_from.to_le_bytes());
-        instruction_memory.extend(length.to_le_bytes());
+        instruction_memory.extend(encode_usize(to_location));
+        instruction_memory.extend(encode_usize(indexing_size));
+        instruction_memory.extend(encode_usize(to_pointer_location));
+        instruction_memory.extend(encode_usize(direct_from));
+        instruction_memory.extend(encode_usize(length));
+        instruction_memory.extend(encode_usize(array_length));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -38,38 +41,48 @@ impl DynamicToCopyInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 5
+        POINTER_SIZE * 6
     }
 
     pub(crate) fn get_debug(data: &[u8]) -> String {
         format!(
-            "DYNAMIC COPY [{}] dest [{}:{}:{}] (len:{})",
-            get_usize(&(size_of::<usize>() * 3), data),
-            get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
-            get_usize(&(size_of::<usize>() * 2), data),
-            get_usize(&(size_of::<usize>() * 4), data),
+            "DYNAMIC COPY [{}] dest [{}:{}:{}] (len:{}) (of:{})",
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
+            format_address(get_usize(&0, data)),
+            get_usize(&POINTER_SIZE, data),
+            format_address(get_usize(&(POINTER_SIZE * 2), data)),
+            get_usize(&(POINTER_SIZE * 4), data),
+            get_usize(&(POINTER_SIZE * 5), data),
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let to_location = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let indexing_size = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let to_pointer = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let direct_from = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let length = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-
-        let actual_to = get_usize(&to_pointer, &memory_managers.variable_memory.memory);
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let instruction_address = *pointer - INSTRUCTION_CODE_LENGTH;
+        let to_location = memory_managers.read_variable_address(pointer)?;
+        let indexing_size = memory_managers.read_operand(pointer)?;
+        let to_pointer = memory_managers.read_variable_address(pointer)?;
+        let direct_from = memory_managers.read_variable_address(pointer)?;
+        let length = memory_managers.read_operand(pointer)?;
+        let array_length = memory_managers.read_operand(pointer)?;
 
-        for i in 0..length {
-            memory_managers.variable_memory.memory[to_location + (actual_to * indexing_size) + i] =
-                memory_managers.variable_memory.memory[direct_from + i];
+        let actual_to = memory_managers.variable_memory.read_usize(to_pointer)?;
+        if actual_to >= array_length {
+            return Err(RuntimeError::IndexOutOfBounds {
+                index: actual_to,
+                length: array_length,
+                address: instruction_address,
+            });
         }
+
+        let element = actual_to
+            .saturating_mul(indexing_size)
+            .saturating_add(to_location);
+        memory_managers
+            .variable_memory
+            .copy(direct_from, element, length)
     }
 }
 
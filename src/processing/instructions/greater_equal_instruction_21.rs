@@ -0,0 +1,86 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::greater_instruction_20::compare_bytes;
+use crate::processing::processor::MemoryManagers;
+use crate::processing::types::boolean::{BOOLEAN_FALSE, BOOLEAN_TRUE};
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
+use std::cmp::Ordering;
+
+pub struct GreaterEqualInstruction {
+    address: usize,
+}
+
+pub const GREATER_EQUAL_INSTRUCTION_CODE: u16 = 21;
+
+/// Checks if LHS is greater than or equal to RHS
+impl GreaterEqualInstruction {
+    pub fn new_alloc(
+        memory_managers: &mut MemoryManagers,
+        lhs: usize,
+        rhs: usize,
+        len: usize,
+        dest: usize,
+        signed: bool,
+    ) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(GREATER_EQUAL_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(lhs));
+        instruction_memory.extend(encode_usize(rhs));
+        instruction_memory.extend(encode_usize(len));
+        instruction_memory.extend(encode_usize(dest));
+        instruction_memory.extend(encode_usize(signed as usize));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        GREATER_EQUAL_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE * 5 // LHS, RHS, len, dest, signed
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!(
+            "GREATER EQUAL [{}], [{}] (len:{}) dest [{}] (signed: {})",
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            get_usize(&(POINTER_SIZE * 2), data),
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
+            get_usize(&(POINTER_SIZE * 4), data) != 0,
+        )
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let lhs = memory_managers.read_variable_address(pointer)?;
+        let rhs = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+        let signed = memory_managers.read_operand(pointer)? != 0;
+
+        let memory = &mut memory_managers.variable_memory;
+        let result = compare_bytes(memory.read(lhs, len)?, memory.read(rhs, len)?, signed);
+        memory.write_byte(
+            dest,
+            if result != Ordering::Less {
+                BOOLEAN_TRUE
+            } else {
+                BOOLEAN_FALSE
+            },
+        )
+    }
+}
+
+impl Instruction for GreaterEqualInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
@@ -1,8 +1,9 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
 use crate::processing::processor::MemoryManagers;
-use crate::util::get_usize;
-use std::io::stdin;
-use std::mem::size_of;
+use crate::program_io::ProgramIo;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct InputInstruction {
     address: usize,
@@ -10,12 +11,21 @@ pub struct InputInstruction {
 
 pub const INPUT_INSTRUCTION_CODE: u16 = 15;
 
+/// Reads a line of input. If `string` is set, `dest` is a string of capacity `size` and the whole
+/// line (excluding the line ending) is read into it. Otherwise `size` bytes are filled, truncating
+/// or zero-padding the line
 impl InputInstruction {
-    pub fn new_alloc(memory_managers: &mut MemoryManagers, size: usize, dest: usize) -> Self {
+    pub fn new_alloc(
+        memory_managers: &mut MemoryManagers,
+        size: usize,
+        dest: usize,
+        string: bool,
+    ) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(INPUT_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(size.to_le_bytes());
-        instruction_memory.extend(dest.to_le_bytes());
+        instruction_memory.extend(encode_usize(size));
+        instruction_memory.extend(encode_usize(dest));
+        instruction_memory.extend(encode_usize(string as usize));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -29,34 +39,53 @@ impl InputInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 2
+        POINTER_SIZE * 3 // len, dest, string
     }
 
     pub fn get_debug(data: &[u8]) -> String {
         format!(
-            "INPUT (len: {}) dest [{}]",
+            "INPUT (len: {}) dest [{}] (string: {})",
             get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            get_usize(&(POINTER_SIZE * 2), data) != 0,
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let len = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let dest = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+        io: &mut dyn ProgramIo,
+    ) -> Result<(), RuntimeError> {
+        let instruction_address = *pointer - INSTRUCTION_CODE_LENGTH;
+        let len = memory_managers.read_operand(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+        let string = memory_managers.read_operand(pointer)? != 0;
 
-        let mut input = String::new();
-        stdin().read_line(&mut input).expect("Terminal read error");
+        let input = io.read_line()?;
 
-        for i in 0..len {
-            if i < input.len() {
-                memory_managers.variable_memory.memory[dest + i] =
-                    input.chars().nth(i).unwrap() as u8;
-            } else {
-                memory_managers.variable_memory.memory[dest + i] = 0;
+        if string {
+            let line = input.trim_end_matches(&['\r', '\n'][..]).as_bytes();
+            if line.len() > len {
+                return Err(RuntimeError::StringOverflow {
+                    length: line.len(),
+                    capacity: len,
+                    address: instruction_address,
+                });
             }
+
+            let size = POINTER_SIZE.saturating_add(len);
+            memory_managers.variable_memory.read(dest, size)?;
+            let mut result = Vec::with_capacity(size);
+            result.extend(encode_usize(line.len()));
+            result.extend(line);
+            result.resize(size, 0);
+            return memory_managers.variable_memory.write(dest, &result);
         }
+
+        memory_managers.variable_memory.read(dest, len)?;
+        let mut result: Vec<u8> = input.chars().take(len).map(|c| c as u8).collect();
+        result.resize(len, 0);
+        memory_managers.variable_memory.write(dest, &result)
     }
 }
 
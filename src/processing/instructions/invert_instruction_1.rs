@@ -1,8 +1,8 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::types::boolean::{BOOLEAN_FALSE, BOOLEAN_TRUE};
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct InvertInstruction {
     address: usize,
@@ -15,8 +15,8 @@ impl InvertInstruction {
     pub fn new_alloc(memory_managers: &mut MemoryManagers, to_flip: usize, dest: usize) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(INVERT_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(to_flip.to_le_bytes());
-        instruction_memory.extend(dest.to_le_bytes());
+        instruction_memory.extend(encode_usize(to_flip));
+        instruction_memory.extend(encode_usize(dest));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -30,27 +30,29 @@ impl InvertInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 2 // To flip, dest
+        POINTER_SIZE * 2 // To flip, dest
     }
 
     pub fn get_debug(data: &[u8]) -> String {
         format!(
             "INVERT [{}] dest [{}]",
-            get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let variable = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let dest = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let variable = memory_managers.read_variable_address(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
 
-        if memory_managers.variable_memory.memory[variable] == BOOLEAN_TRUE {
-            memory_managers.variable_memory.memory[dest] = BOOLEAN_FALSE;
+        let memory = &mut memory_managers.variable_memory;
+        if memory.read_byte(variable)? == BOOLEAN_TRUE {
+            memory.write_byte(dest, BOOLEAN_FALSE)
         } else {
-            memory_managers.variable_memory.memory[dest] = BOOLEAN_TRUE;
+            memory.write_byte(dest, BOOLEAN_TRUE)
         }
     }
 }
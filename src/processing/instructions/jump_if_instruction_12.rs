@@ -1,16 +1,16 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::types::boolean::BOOLEAN_TRUE;
 use crate::processing::types::{Type, TypeSymbol};
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct JumpIfInstruction {
     address: usize,
 }
 
-pub const JUMP_IF_NOT_INSTRUCTION_CODE: u16 = 12;
+pub const JUMP_IF_INSTRUCTION_CODE: u16 = 12;
 
 impl JumpIfInstruction {
     pub fn new_alloc(
@@ -23,9 +23,9 @@ impl JumpIfInstruction {
         }
 
         let mut instruction_memory = vec![];
-        instruction_memory.extend(JUMP_IF_NOT_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(condition_boolean.get_address().to_le_bytes());
-        instruction_memory.extend(dest.to_le_bytes());
+        instruction_memory.extend(JUMP_IF_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(condition_boolean.get_address()));
+        instruction_memory.extend(encode_usize(dest));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -36,35 +36,38 @@ impl JumpIfInstruction {
 
     pub fn set_destination(&self, memory_managers: &mut MemoryManagers, dest: usize) {
         memory_managers.program_memory.overwrite(
-            self.address + INSTRUCTION_CODE_LENGTH + size_of::<usize>(),
-            &dest.to_le_bytes(),
+            self.address + INSTRUCTION_CODE_LENGTH + POINTER_SIZE,
+            &encode_usize(dest),
         )
     }
 
     pub fn get_code() -> u16 {
-        JUMP_IF_NOT_INSTRUCTION_CODE
+        JUMP_IF_INSTRUCTION_CODE
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 2 // Condition, dest
+        POINTER_SIZE * 2 // Condition, dest
     }
 
     pub fn get_debug(data: &[u8]) -> String {
         format!(
             "JUMP IF [{}] goto [{}]",
-            get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
+            format_address(get_usize(&0, data)),
+            get_usize(&POINTER_SIZE, data),
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let condition = get_usize(pointer, &memory_managers.program_memory.memory);
-        if memory_managers.variable_memory.memory[condition] == BOOLEAN_TRUE {
-            *pointer += size_of::<usize>();
-            *pointer = get_usize(pointer, &memory_managers.program_memory.memory);
-        } else {
-            *pointer += size_of::<usize>() * 2;
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let condition = memory_managers.read_variable_address(pointer)?;
+        let destination = memory_managers.read_operand(pointer)?;
+
+        if memory_managers.variable_memory.read_byte(condition)? == BOOLEAN_TRUE {
+            *pointer = destination;
         }
+        Ok(())
     }
 }
 
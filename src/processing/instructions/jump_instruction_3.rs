@@ -1,8 +1,8 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
 use crate::processing::processor::MemoryManagers;
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, get_usize, POINTER_SIZE};
 
 pub struct JumpInstruction {
     address: usize,
@@ -14,7 +14,7 @@ impl JumpInstruction {
     pub fn new_alloc(memory_managers: &mut MemoryManagers, dest: usize) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(JUMP_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(dest.to_le_bytes());
+        instruction_memory.extend(encode_usize(dest));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -26,7 +26,7 @@ impl JumpInstruction {
     pub fn set_destination(&self, memory_managers: &mut MemoryManagers, dest: usize) {
         memory_managers
             .program_memory
-            .overwrite(self.address + INSTRUCTION_CODE_LENGTH, &dest.to_le_bytes())
+            .overwrite(self.address + INSTRUCTION_CODE_LENGTH, &encode_usize(dest))
     }
 
     pub fn get_code() -> u16 {
@@ -34,15 +34,19 @@ impl JumpInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() // dest
+        POINTER_SIZE // dest
     }
 
     pub fn get_debug(data: &[u8]) -> String {
         format!("JUMP [{}]", get_usize(&0, data),)
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        *pointer = get_usize(pointer, &memory_managers.program_memory.memory);
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        *pointer = memory_managers.read_operand(pointer)?;
+        Ok(())
     }
 }
 
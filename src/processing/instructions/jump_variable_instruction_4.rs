@@ -1,8 +1,8 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
 use crate::processing::processor::MemoryManagers;
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct JumpVariableInstruction {
     address: usize,
@@ -14,7 +14,7 @@ impl JumpVariableInstruction {
     pub fn new_alloc(memory_managers: &mut MemoryManagers, dest_variable: usize) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(JUMP_VARIABLE_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(dest_variable.to_le_bytes());
+        instruction_memory.extend(encode_usize(dest_variable));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -25,8 +25,8 @@ impl JumpVariableInstruction {
 
     pub fn set_destination(&self, memory_managers: &mut MemoryManagers, dest: usize) {
         memory_managers.program_memory.overwrite(
-            self.address + INSTRUCTION_CODE_LENGTH + size_of::<usize>(),
-            &dest.to_le_bytes(),
+            self.address + INSTRUCTION_CODE_LENGTH + POINTER_SIZE,
+            &encode_usize(dest),
         )
     }
 
@@ -35,19 +35,23 @@ impl JumpVariableInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() // Variable
+        POINTER_SIZE // Variable
     }
 
     pub fn get_debug(data: &[u8]) -> String {
-        format!("JUMP to variable [{}]", get_usize(&0, data),)
+        format!("JUMP to variable [{}]", format_address(get_usize(&0, data)),)
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let destination_variable = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer = get_usize(
-            &destination_variable,
-            &memory_managers.variable_memory.memory,
-        );
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let destination_variable = memory_managers.read_variable_address(pointer)?;
+
+        *pointer = memory_managers
+            .variable_memory
+            .read_usize(destination_variable)?;
+        Ok(())
     }
 }
 
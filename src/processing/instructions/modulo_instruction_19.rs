@@ -0,0 +1,88 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::divide_instruction_18::divide_bytes;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
+use crate::processing::processor::MemoryManagers;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
+
+pub struct ModuloInstruction {
+    address: usize,
+}
+
+pub const MODULO_INSTRUCTION_CODE: u16 = 19;
+
+/// Gets the remainder of LHS divided by RHS. The result takes the sign of LHS
+impl ModuloInstruction {
+    pub fn new_alloc(
+        memory_managers: &mut MemoryManagers,
+        lhs: usize,
+        rhs: usize,
+        len: usize,
+        dest: usize,
+        signed: bool,
+    ) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(MODULO_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(lhs));
+        instruction_memory.extend(encode_usize(rhs));
+        instruction_memory.extend(encode_usize(len));
+        instruction_memory.extend(encode_usize(dest));
+        instruction_memory.extend(encode_usize(signed as usize));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        MODULO_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE * 5 // LHS, RHS, len, dest, signed
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!(
+            "MODULO [{} by {}] (len: {}) dest [{}] (signed: {})",
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            get_usize(&(POINTER_SIZE * 2), data),
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
+            get_usize(&(POINTER_SIZE * 4), data) != 0,
+        )
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let instruction_address = *pointer - INSTRUCTION_CODE_LENGTH;
+        let lhs = memory_managers.read_variable_address(pointer)?;
+        let rhs = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+        let signed = memory_managers.read_operand(pointer)? != 0;
+
+        let memory = &memory_managers.variable_memory;
+        let (_, remainder) =
+            match divide_bytes(memory.read(lhs, len)?, memory.read(rhs, len)?, signed) {
+                None => {
+                    return Err(RuntimeError::ModuloByZero {
+                        address: instruction_address,
+                    })
+                }
+                Some(value) => value,
+            };
+
+        memory_managers.variable_memory.write(dest, &remainder)
+    }
+}
+
+impl Instruction for ModuloInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
@@ -0,0 +1,89 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::processor::MemoryManagers;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
+
+pub struct MultiplyInstruction {
+    address: usize,
+}
+
+pub const MULTIPLY_INSTRUCTION_CODE: u16 = 17;
+
+impl MultiplyInstruction {
+    pub fn new_alloc(
+        memory_managers: &mut MemoryManagers,
+        lhs: usize,
+        rhs: usize,
+        len: usize,
+        dest: usize,
+    ) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(MULTIPLY_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(lhs));
+        instruction_memory.extend(encode_usize(rhs));
+        instruction_memory.extend(encode_usize(len));
+        instruction_memory.extend(encode_usize(dest));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        MULTIPLY_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE * 4
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!(
+            "MULTIPLY [{} by {}] (len: {}) dest [{}]",
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            get_usize(&(POINTER_SIZE * 2), data),
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
+        )
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let lhs = memory_managers.read_variable_address(pointer)?;
+        let rhs = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+
+        let memory = &memory_managers.variable_memory;
+        let lhs = memory.read(lhs, len)?;
+        let rhs = memory.read(rhs, len)?;
+
+        // Only the lowest `len` bytes are kept - identical for signed and unsigned values
+        let mut result = vec![0u8; len];
+        for (i, a) in lhs.iter().enumerate() {
+            let a = *a as u16;
+            if a == 0 {
+                continue;
+            }
+
+            let mut carry: u16 = 0;
+            for (j, b) in rhs[..(len - i)].iter().enumerate() {
+                let product = a * *b as u16 + result[i + j] as u16 + carry;
+                result[i + j] = product as u8;
+                carry = product >> 8;
+            }
+        }
+
+        memory_managers.variable_memory.write(dest, &result)
+    }
+}
+
+impl Instruction for MultiplyInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
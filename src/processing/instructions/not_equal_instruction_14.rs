@@ -1,8 +1,8 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::types::boolean::{BOOLEAN_FALSE, BOOLEAN_TRUE};
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct NotEqualInstruction {
     address: usize,
@@ -21,10 +21,10 @@ impl NotEqualInstruction {
     ) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(NOT_EQUAL_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(lhs.to_le_bytes());
-        instruction_memory.extend(rhs.to_le_bytes());
-        instruction_memory.extend(len.to_le_bytes());
-        instruction_memory.extend(dest.to_le_bytes());
+        instruction_memory.extend(encode_usize(lhs));
+        instruction_memory.extend(encode_usize(rhs));
+        instruction_memory.extend(encode_usize(len));
+        instruction_memory.extend(encode_usize(dest));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -38,38 +38,34 @@ impl NotEqualInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 4 // LHS, RHS, len, dest
+        POINTER_SIZE * 4 // LHS, RHS, len, dest
     }
 
     pub fn get_debug(data: &[u8]) -> String {
         format!(
             "NOT EQUAL [{}], [{}] (len:{}) dest [{}]",
-            get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
-            get_usize(&(size_of::<usize>() * 2), data),
-            get_usize(&(size_of::<usize>() * 3), data),
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            get_usize(&(POINTER_SIZE * 2), data),
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let lhs = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let rhs = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let len = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let dest = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let lhs = memory_managers.read_variable_address(pointer)?;
+        let rhs = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
 
-        for i in 0..len {
-            if memory_managers.variable_memory.memory[lhs + i]
-                != memory_managers.variable_memory.memory[rhs + i]
-            {
-                memory_managers.variable_memory.memory[dest] = BOOLEAN_TRUE;
-                return;
-            }
+        let memory = &mut memory_managers.variable_memory;
+        if memory.read(lhs, len)? == memory.read(rhs, len)? {
+            memory.write_byte(dest, BOOLEAN_FALSE)
+        } else {
+            memory.write_byte(dest, BOOLEAN_TRUE)
         }
-        memory_managers.variable_memory.memory[dest] = BOOLEAN_FALSE;
     }
 }
 
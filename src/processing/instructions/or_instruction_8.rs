@@ -1,8 +1,8 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::types::boolean::{BOOLEAN_FALSE, BOOLEAN_TRUE};
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct OrInstruction {
     address: usize,
@@ -20,9 +20,9 @@ impl OrInstruction {
     ) -> Self {
         let mut instruction_memory = vec![];
         instruction_memory.extend(OR_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(lhs.to_le_bytes());
-        instruction_memory.extend(rhs.to_le_bytes());
-        instruction_memory.extend(dest.to_le_bytes());
+        instruction_memory.extend(encode_usize(lhs));
+        instruction_memory.extend(encode_usize(rhs));
+        instruction_memory.extend(encode_usize(dest));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -36,32 +36,33 @@ impl OrInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 3 // LHS, RHS, dest
+        POINTER_SIZE * 3 // LHS, RHS, dest
     }
 
     pub fn get_debug(data: &[u8]) -> String {
         format!(
             "OR [{}], [{}] dest [{}]",
-            get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
-            get_usize(&(size_of::<usize>() * 2), data),
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            format_address(get_usize(&(POINTER_SIZE * 2), data)),
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let lhs = memory_managers.variable_memory.memory
-            [get_usize(pointer, &memory_managers.program_memory.memory)];
-        *pointer += size_of::<usize>();
-        let rhs = memory_managers.variable_memory.memory
-            [get_usize(pointer, &memory_managers.program_memory.memory)];
-        *pointer += size_of::<usize>();
-        let dest = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let lhs = memory_managers.read_variable_address(pointer)?;
+        let rhs = memory_managers.read_variable_address(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
 
+        let memory = &mut memory_managers.variable_memory;
+        let lhs = memory.read_byte(lhs)?;
+        let rhs = memory.read_byte(rhs)?;
         if lhs == BOOLEAN_TRUE || rhs == BOOLEAN_TRUE {
-            memory_managers.variable_memory.memory[dest] = BOOLEAN_TRUE;
+            memory.write_byte(dest, BOOLEAN_TRUE)
         } else {
-            memory_managers.variable_memory.memory[dest] = BOOLEAN_FALSE;
+            memory.write_byte(dest, BOOLEAN_FALSE)
         }
     }
 }
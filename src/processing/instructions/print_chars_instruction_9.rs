@@ -1,8 +1,9 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::types::Type;
-use crate::util::get_usize;
-use std::mem::size_of;
+use crate::program_io::ProgramIo;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 
 pub struct PrintCharsInstruction {
     address: usize,
@@ -18,9 +19,9 @@ impl PrintCharsInstruction {
 
         let mut instruction_memory = vec![];
         instruction_memory.extend(PRINT_CHARS_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(to_print.get_address().to_le_bytes());
-        instruction_memory.extend(to_print.get_size().to_le_bytes());
-        instruction_memory.extend(length.to_le_bytes());
+        instruction_memory.extend(encode_usize(to_print.get_address()));
+        instruction_memory.extend(encode_usize(to_print.get_size()));
+        instruction_memory.extend(encode_usize(length));
 
         assert_eq!(instruction_memory.len() - 2, Self::get_size());
 
@@ -34,31 +35,31 @@ impl PrintCharsInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 3 // Address, Len
+        POINTER_SIZE * 3 // Address, Len
     }
 
     pub(crate) fn get_debug(data: &[u8]) -> String {
         format!(
             "PRINT CHARS [{}] (len:{},{})",
-            get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
-            get_usize(&(size_of::<usize>() * 2), data),
+            format_address(get_usize(&0, data)),
+            get_usize(&POINTER_SIZE, data),
+            get_usize(&(POINTER_SIZE * 2), data),
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let position = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let len = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let count = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        print!(
-            "{}",
-            String::from_utf8_lossy(
-                &memory_managers.variable_memory.memory[position..position + (len * count)]
-            )
-        );
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+        io: &mut dyn ProgramIo,
+    ) -> Result<(), RuntimeError> {
+        let position = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
+        let count = memory_managers.read_operand(pointer)?;
+
+        let data = memory_managers
+            .variable_memory
+            .read(position, len.saturating_mul(count))?;
+        io.print(&String::from_utf8_lossy(data))
     }
 }
 
@@ -1,7 +1,9 @@
 use super::Instruction;
+use crate::errors::RuntimeError;
 use crate::processing::processor::MemoryManagers;
-use crate::processing::types::Type;
-use crate::util::get_usize;
+use crate::processing::types::{Type, TypeSymbol};
+use crate::program_io::ProgramIo;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
 use std::mem::size_of;
 
 pub struct PrintInstruction {
@@ -18,9 +20,12 @@ impl PrintInstruction {
 
         let mut instruction_memory = vec![];
         instruction_memory.extend(PRINT_INSTRUCTION_CODE.to_le_bytes());
-        instruction_memory.extend(to_print.get_address().to_le_bytes());
-        instruction_memory.extend(to_print.get_size().to_le_bytes());
-        instruction_memory.extend(length.to_le_bytes());
+        instruction_memory.extend(encode_usize(to_print.get_address()));
+        instruction_memory.extend(encode_usize(to_print.get_size()));
+        instruction_memory.extend(encode_usize(length));
+        instruction_memory.extend(encode_usize(
+            (to_print.get_type() == TypeSymbol::Integer) as usize,
+        ));
 
         // println!("{}", to_print.get_size() * length);
 
@@ -36,39 +41,58 @@ impl PrintInstruction {
     }
 
     pub fn get_size() -> usize {
-        size_of::<usize>() * 3 // Address, LEn
+        POINTER_SIZE * 4 // Address, Len, Count, Signed
     }
 
     pub(crate) fn get_debug(data: &[u8]) -> String {
         format!(
-            "PRINT [{}] (len:{},{})",
-            get_usize(&0, data),
-            get_usize(&size_of::<usize>(), data),
-            get_usize(&(size_of::<usize>() * 2), data),
+            "PRINT [{}] (len:{},{}) (signed:{})",
+            format_address(get_usize(&0, data)),
+            get_usize(&POINTER_SIZE, data),
+            get_usize(&(POINTER_SIZE * 2), data),
+            get_usize(&(POINTER_SIZE * 3), data) != 0,
         )
     }
 
-    pub fn execute(pointer: &mut usize, memory_managers: &mut MemoryManagers) {
-        let position = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let len = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-        let count = get_usize(pointer, &memory_managers.program_memory.memory);
-        *pointer += size_of::<usize>();
-
-        let data = &memory_managers.variable_memory.memory[position..(position + (count * len))];
-
-        if data.len() == 8 {
-            println!(
-                "{}",
-                usize::from_le_bytes((&memory_managers.variable_memory.memory[position..(position + (count * len))]).try_into().unwrap())
-            );
-        }
-        else {
-            println!(
-                "{:X?}",
-                &memory_managers.variable_memory.memory[position..(position + (count * len))]
-            );
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+        io: &mut dyn ProgramIo,
+    ) -> Result<(), RuntimeError> {
+        let position = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
+        let count = memory_managers.read_operand(pointer)?;
+        let signed = memory_managers.read_operand(pointer)? != 0;
+
+        let data = memory_managers
+            .variable_memory
+            .read(position, count.saturating_mul(len))?;
+
+        if signed && len != 0 && len <= size_of::<i128>() {
+            // Sign extend each little-endian value
+            let values: Vec<i128> = data
+                .chunks(len)
+                .map(|value| {
+                    let fill = if value.last().copied().unwrap_or(0) & 0x80 != 0 {
+                        0xFF
+                    } else {
+                        0x00
+                    };
+                    let mut bytes = [fill; size_of::<i128>()];
+                    bytes[..value.len()].copy_from_slice(value);
+                    i128::from_le_bytes(bytes)
+                })
+                .collect();
+
+            if values.len() == 1 {
+                io.print(&format!("{}\n", values[0]))
+            } else {
+                io.print(&format!("{:?}\n", values))
+            }
+        } else if data.len() == POINTER_SIZE {
+            io.print(&format!("{}\n", get_usize(&0, data)))
+        } else {
+            io.print(&format!("{:X?}\n", data))
         }
     }
 }
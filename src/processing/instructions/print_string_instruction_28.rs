@@ -0,0 +1,57 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::processor::MemoryManagers;
+use crate::program_io::ProgramIo;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
+
+pub struct PrintStringInstruction {
+    address: usize,
+}
+
+pub const PRINT_STRING_INSTRUCTION_CODE: u16 = 28;
+
+/// Prints the characters of a string up to its length
+impl PrintStringInstruction {
+    pub fn new_alloc(memory_managers: &mut MemoryManagers, string: usize) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(PRINT_STRING_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(string));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        PRINT_STRING_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE // string
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!("PRINT STRING [{}]", format_address(get_usize(&0, data)))
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+        io: &mut dyn ProgramIo,
+    ) -> Result<(), RuntimeError> {
+        let string = memory_managers.read_variable_address(pointer)?;
+
+        let memory = &memory_managers.variable_memory;
+        let len = memory.read_usize(string)?;
+        let data = memory.read(string + POINTER_SIZE, len)?;
+        io.print(&String::from_utf8_lossy(data))
+    }
+}
+
+impl Instruction for PrintStringInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
@@ -0,0 +1,59 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
+use crate::processing::processor::MemoryManagers;
+use crate::util::{encode_usize, get_usize, POINTER_SIZE};
+
+pub struct ReserveFrameInstruction {
+    address: usize,
+}
+
+pub const RESERVE_FRAME_INSTRUCTION_CODE: u16 = 24;
+
+/// Reserves space for the local variables of the current stack frame
+impl ReserveFrameInstruction {
+    pub fn new_alloc(memory_managers: &mut MemoryManagers, size: usize) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(RESERVE_FRAME_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(size));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn set_size(&self, memory_managers: &mut MemoryManagers, size: usize) {
+        memory_managers
+            .program_memory
+            .overwrite(self.address + INSTRUCTION_CODE_LENGTH, &encode_usize(size))
+    }
+
+    pub fn get_code() -> u16 {
+        RESERVE_FRAME_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE // size
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!("RESERVE FRAME (size: {})", get_usize(&0, data))
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let size = memory_managers.read_operand(pointer)?;
+
+        memory_managers.variable_memory.grow_stack(size)
+    }
+}
+
+impl Instruction for ReserveFrameInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
@@ -0,0 +1,49 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::processor::MemoryManagers;
+
+pub struct ReturnInstruction {
+    address: usize,
+}
+
+pub const RETURN_INSTRUCTION_CODE: u16 = 23;
+
+/// Pops the current stack frame and jumps back to the caller
+impl ReturnInstruction {
+    pub fn new_alloc(memory_managers: &mut MemoryManagers) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(RETURN_INSTRUCTION_CODE.to_le_bytes());
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        RETURN_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        0
+    }
+
+    pub fn get_debug(_data: &[u8]) -> String {
+        "RETURN".to_string()
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        *pointer = memory_managers.variable_memory.pop_frame()?;
+        Ok(())
+    }
+}
+
+impl Instruction for ReturnInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
@@ -0,0 +1,98 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
+use crate::processing::processor::MemoryManagers;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
+
+pub struct SliceInstruction {
+    address: usize,
+}
+
+pub const SLICE_INSTRUCTION_CODE: u16 = 27;
+
+/// Copies the characters of a string from a start index (inclusive) to an end index (exclusive)
+/// into a new string
+impl SliceInstruction {
+    pub fn new_alloc(
+        memory_managers: &mut MemoryManagers,
+        string: usize,
+        start: usize,
+        end: usize,
+        dest: usize,
+        capacity: usize,
+    ) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(SLICE_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(string));
+        instruction_memory.extend(encode_usize(start));
+        instruction_memory.extend(encode_usize(end));
+        instruction_memory.extend(encode_usize(dest));
+        instruction_memory.extend(encode_usize(capacity));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        SLICE_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE * 5 // string, start, end, dest, capacity
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!(
+            "SLICE [{}] from [{}] to [{}] dest [{}] (capacity: {})",
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            format_address(get_usize(&(POINTER_SIZE * 2), data)),
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
+            get_usize(&(POINTER_SIZE * 4), data),
+        )
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let instruction_address = *pointer - INSTRUCTION_CODE_LENGTH;
+        let string = memory_managers.read_variable_address(pointer)?;
+        let start = memory_managers.read_variable_address(pointer)?;
+        let end = memory_managers.read_variable_address(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+        let capacity = memory_managers.read_operand(pointer)?;
+
+        let memory = &memory_managers.variable_memory;
+        let len = memory.read_usize(string)?;
+        let start = memory.read_usize(start)?;
+        let end = memory.read_usize(end)?;
+        if start > end || end > len || end - start > capacity {
+            return Err(RuntimeError::SliceOutOfBounds {
+                start,
+                end,
+                length: len,
+                address: instruction_address,
+            });
+        }
+
+        // Build result separately as the destination may be the string being sliced
+        let size = POINTER_SIZE.saturating_add(capacity);
+        memory.read(dest, size)?;
+        let mut result = Vec::with_capacity(size);
+        result.extend(encode_usize(end - start));
+        result.extend(&memory.read(string + POINTER_SIZE, len)?[start..end]);
+        result.resize(size, 0);
+
+        memory_managers.variable_memory.write(dest, &result)
+    }
+}
+
+impl Instruction for SliceInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
@@ -0,0 +1,80 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
+use crate::processing::processor::MemoryManagers;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
+
+pub struct StringIndexInstruction {
+    address: usize,
+}
+
+pub const STRING_INDEX_INSTRUCTION_CODE: u16 = 26;
+
+/// Copies the character at an index of a string, checking the index against the string's length
+impl StringIndexInstruction {
+    pub fn new_alloc(
+        memory_managers: &mut MemoryManagers,
+        string: usize,
+        index: usize,
+        dest: usize,
+    ) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(STRING_INDEX_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(string));
+        instruction_memory.extend(encode_usize(index));
+        instruction_memory.extend(encode_usize(dest));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        STRING_INDEX_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE * 3 // string, index, dest
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!(
+            "STRING INDEX [{}] at [{}] dest [{}]",
+            format_address(get_usize(&0, data)),
+            format_address(get_usize(&POINTER_SIZE, data)),
+            format_address(get_usize(&(POINTER_SIZE * 2), data)),
+        )
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let instruction_address = *pointer - INSTRUCTION_CODE_LENGTH;
+        let string = memory_managers.read_variable_address(pointer)?;
+        let index = memory_managers.read_variable_address(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+
+        let memory = &mut memory_managers.variable_memory;
+        let len = memory.read_usize(string)?;
+        let index = memory.read_usize(index)?;
+        if index >= len {
+            return Err(RuntimeError::IndexOutOfBounds {
+                index,
+                length: len,
+                address: instruction_address,
+            });
+        }
+
+        let character = memory.read(string + POINTER_SIZE, len)?[index];
+        memory.write_byte(dest, character)
+    }
+}
+
+impl Instruction for StringIndexInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
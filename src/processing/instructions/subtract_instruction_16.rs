@@ -0,0 +1,85 @@
+use super::Instruction;
+use crate::errors::RuntimeError;
+use crate::processing::processor::MemoryManagers;
+use crate::util::{encode_usize, format_address, get_usize, POINTER_SIZE};
+
+pub struct SubtractInstruction {
+    address: usize,
+}
+
+pub const SUBTRACT_INSTRUCTION_CODE: u16 = 16;
+
+impl SubtractInstruction {
+    pub fn new_alloc(
+        memory_managers: &mut MemoryManagers,
+        lhs: usize,
+        rhs: usize,
+        len: usize,
+        dest: usize,
+    ) -> Self {
+        let mut instruction_memory = vec![];
+        instruction_memory.extend(SUBTRACT_INSTRUCTION_CODE.to_le_bytes());
+        instruction_memory.extend(encode_usize(lhs));
+        instruction_memory.extend(encode_usize(rhs));
+        instruction_memory.extend(encode_usize(len));
+        instruction_memory.extend(encode_usize(dest));
+
+        assert_eq!(instruction_memory.len() - 2, Self::get_size());
+
+        let address = memory_managers.program_memory.append(&instruction_memory);
+
+        Self { address }
+    }
+
+    pub fn get_code() -> u16 {
+        SUBTRACT_INSTRUCTION_CODE
+    }
+
+    pub fn get_size() -> usize {
+        POINTER_SIZE * 4
+    }
+
+    pub fn get_debug(data: &[u8]) -> String {
+        format!(
+            "SUBTRACT [{} from {}] (len: {}) dest [{}]",
+            format_address(get_usize(&POINTER_SIZE, data)),
+            format_address(get_usize(&0, data)),
+            get_usize(&(POINTER_SIZE * 2), data),
+            format_address(get_usize(&(POINTER_SIZE * 3), data)),
+        )
+    }
+
+    pub fn execute(
+        pointer: &mut usize,
+        memory_managers: &mut MemoryManagers,
+    ) -> Result<(), RuntimeError> {
+        let lhs = memory_managers.read_variable_address(pointer)?;
+        let rhs = memory_managers.read_variable_address(pointer)?;
+        let len = memory_managers.read_operand(pointer)?;
+        let dest = memory_managers.read_variable_address(pointer)?;
+
+        let memory = &memory_managers.variable_memory;
+        let lhs = memory.read(lhs, len)?;
+        let rhs = memory.read(rhs, len)?;
+        let mut result = Vec::with_capacity(len);
+        let mut borrow: i16 = 0;
+        for (a, b) in lhs.iter().zip(rhs) {
+            let mut difference = *a as i16 - *b as i16 - borrow;
+            borrow = 0;
+            if difference < 0 {
+                difference += 0x100;
+                borrow = 1;
+            }
+
+            result.push(difference as u8);
+        }
+
+        memory_managers.variable_memory.write(dest, &result)
+    }
+}
+
+impl Instruction for SubtractInstruction {
+    fn get_address(&self) -> usize {
+        self.address
+    }
+}
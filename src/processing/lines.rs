@@ -9,7 +9,10 @@ pub mod function_line;
 pub mod if_line;
 pub mod indexed_variable_assignment_line;
 pub mod input_line;
+pub mod loop_line;
 pub mod print_line;
+pub mod return_line;
+pub mod struct_line;
 pub mod variable_assignment_line;
 pub mod variable_initialisation_line;
 pub mod variable_initialisation_with_argument_line;
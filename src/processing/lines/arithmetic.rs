@@ -1,357 +1,612 @@
+use crate::errors::{create_op_not_impl_error, CompileError, Span};
 use crate::processing::processor::MemoryManagers;
 use crate::processing::reference_manager::ReferenceStack;
-use crate::processing::symbols::{Punctuation, Symbol};
-use crate::processing::types::{get_type, get_type_from_literal, Type};
+use crate::processing::symbols::{Literal, Operator, Punctuation, Symbol};
+use crate::processing::types::{
+    get_scoped_type, get_type, get_type_from_literal_with_hint, Type, TypeSymbol,
+};
 
-//noinspection RsLift
-/// Takes an evaluable expression
-///
-/// # Arguments
-///
-/// * `section` - Expression to evaluate
-/// * `to_overwrite` - Type to write result to
-/// * `must_evaluate` - Whether the expression must evaluate to a type
-///
-/// # Returns
-/// * Returns `Ok(Some(Type))` if to_overwrite is `None`.
-/// * Returns `Ok(None)` if to_overwrite is `Some`,
-pub fn handle_arithmetic_section(
-    memory_managers: &mut MemoryManagers,
-    reference_stack: &ReferenceStack,
-    section: &[Symbol],
-    to_overwrite: Option<&Type>,
-    must_evaluate: bool,
-) -> Result<Option<Type>, String> {
-    fn get_formatting_error() -> String {
-        "Operations must be formatted:\n\
-            \t[LHS] [Operator] [RHS] or\n\
-            \t[Operator] [LHS] or\n\
-            \t[Value] or\n\
-            \t[Name][Index] or\n\
-            \t[Function Name][Arguments]"
-            .to_string()
+/// A parsed expression. Names and literals keep their spans so errors can point at them
+enum Expression<'a> {
+    Name(&'a str, Span),
+    Literal(&'a Literal, Span),
+    Call(&'a str, Span, Vec<Expression<'a>>),
+    Index(&'a str, Span, Box<Expression<'a>>),
+    Slice(&'a str, Span, Box<Expression<'a>>, Box<Expression<'a>>),
+    Prefix(Operator, Box<Expression<'a>>),
+    Infix(Box<Expression<'a>>, Operator, Box<Expression<'a>>),
+}
+
+impl Expression<'_> {
+    fn is_literal(&self) -> bool {
+        matches!(self, Expression::Literal(..))
     }
+}
 
-    if section.len() > 3 || section.is_empty() {
-        return Err(get_formatting_error());
+/// Gets the binding strength of a binary operator. Higher binds tighter
+fn get_precedence(operator: &Operator) -> Option<u8> {
+    match operator {
+        Operator::Or => Some(1),
+        Operator::And => Some(2),
+        Operator::Equal | Operator::NotEqual => Some(3),
+        Operator::Greater | Operator::Less | Operator::GreaterEqual | Operator::LessEqual => {
+            Some(4)
+        }
+        Operator::Add | Operator::Subtract => Some(5),
+        Operator::Product | Operator::Divide | Operator::Modulo => Some(6),
+        Operator::Not => None,
     }
+}
+
+/// Whether an operator's result has the same type as its operands
+fn is_arithmetic(operator: &Operator) -> bool {
+    matches!(
+        operator,
+        Operator::Add
+            | Operator::Subtract
+            | Operator::Product
+            | Operator::Divide
+            | Operator::Modulo
+    )
+}
+
+/// Precedence climbing parser over a section of symbols
+struct ExpressionParser<'a> {
+    symbols: &'a [Symbol],
+    position: usize,
+}
+
+impl<'a> ExpressionParser<'a> {
+    /// Parses an entire section into a single expression
+    fn parse(symbols: &'a [Symbol]) -> Result<Expression<'a>, CompileError> {
+        if symbols.is_empty() {
+            return Err(CompileError::Syntax("Expected an expression".to_string()));
+        }
 
-    //? Three operators - [LHS] [Operator] [RHS]
-    if section.len() == 3 {
-        // Get operator
-        let operator = match section[1] {
-            Symbol::Operator(op) => op,
-            _ => return Err(get_formatting_error()),
+        let mut parser = Self {
+            symbols,
+            position: 0,
         };
+        let expression = parser.parse_expression(0)?;
 
-        // Get lhs
-        let mut _lhs_holder = None;
-        let lhs = match &section[0] {
-            Symbol::Name(name) => reference_stack.get_variable(name)?,
-            Symbol::Literal(literal) => {
-                let object = get_type_from_literal(literal, memory_managers)?;
-                object.static_assign_literal(memory_managers, literal)?;
-                _lhs_holder = Some(object);
-                _lhs_holder.as_ref().unwrap()
-            }
-            Symbol::ArithmeticBlock(symbols) => {
-                match handle_arithmetic_section(
-                    memory_managers,
-                    reference_stack,
-                    symbols,
-                    None,
-                    true,
-                ) {
-                    Err(e) => return Err(e),
-                    Ok(object) => {
-                        _lhs_holder = Some(object.unwrap());
-                        _lhs_holder.as_ref().unwrap()
-                    }
+        if let Some(symbol) = parser.peek() {
+            return Err(CompileError::Syntax(format!(
+                "Unexpected {} after end of expression - expected an operator",
+                symbol
+            )));
+        }
+        Ok(expression)
+    }
+
+    fn peek(&self) -> Option<&'a Symbol> {
+        self.symbols.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&'a Symbol> {
+        let symbol = self.symbols.get(self.position);
+        self.position += 1;
+        symbol
+    }
+
+    fn parse_expression(&mut self, min_precedence: u8) -> Result<Expression<'a>, CompileError> {
+        let mut lhs = self.parse_operand()?;
+
+        while let Some(symbol) = self.peek() {
+            let operator = match symbol {
+                Symbol::Operator(operator) => *operator,
+                _ => {
+                    return Err(CompileError::Syntax(format!(
+                        "Expected an operator between values but found {}",
+                        symbol
+                    )))
                 }
-            }
-            _ => {
-                return Err(
-                    "LHS must be a Name, Literal or an operation within brackets".to_string(),
-                )
-            }
-        };
+            };
 
-        // Get rhs
-        let mut _rhs_holder = None;
-        let rhs = match &section[2] {
-            Symbol::Name(name) => reference_stack.get_variable(name)?,
-            Symbol::Literal(literal) => {
-                let object = match get_type_from_literal(literal, memory_managers) {
-                    Err(e) => return Err(e),
-                    Ok(o) => o,
-                };
-                object.static_assign_literal(memory_managers, literal)?;
-                _rhs_holder = Some(object);
-                _rhs_holder.as_ref().unwrap()
-            }
-            Symbol::ArithmeticBlock(symbols) => {
-                match handle_arithmetic_section(
-                    memory_managers,
-                    reference_stack,
-                    symbols,
-                    None,
-                    true,
-                ) {
-                    Err(e) => return Err(e),
-                    Ok(object) => {
-                        _rhs_holder = Some(object.unwrap());
-                        _rhs_holder.as_ref().unwrap()
-                    }
+            let precedence = match get_precedence(&operator) {
+                Some(precedence) => precedence,
+                None => {
+                    return Err(CompileError::Syntax(format!(
+                        "'{}' cannot be used between two values",
+                        operator
+                    )))
                 }
+            };
+            if precedence < min_precedence {
+                break;
             }
-            _ => {
-                return Err(
-                    "RHS must be a Name, Literal or an operation within brackets".to_string(),
-                )
-            }
-        };
+            self.next();
 
-        // Return result
-        if to_overwrite.is_none() {
-            let result_type = lhs.get_operation_return_type(&operator, Some(rhs))?;
+            // All binary operators are left associative
+            let rhs = self.parse_expression(precedence + 1)?;
+            lhs = Expression::Infix(Box::new(lhs), operator, Box::new(rhs));
+        }
 
-            let result = get_type(&result_type, memory_managers)?;
-            lhs.operate(memory_managers, operator, Some(rhs), &result)?;
+        Ok(lhs)
+    }
 
-            Ok(Some(result))
-        } else {
-            lhs.operate(memory_managers, operator, Some(rhs), to_overwrite.unwrap())?;
+    fn parse_operand(&mut self) -> Result<Expression<'a>, CompileError> {
+        let symbol = match self.next() {
+            Some(symbol) => symbol,
+            None => {
+                return Err(CompileError::Syntax(
+                    "Expected a value after operator".to_string(),
+                ))
+            }
+        };
 
-            Ok(None)
-        }
-    }
-    //? Two symbols - function calling, indexing or prefix operators
-    else if section.len() == 2 {
-        //? Function call
-        if let (Symbol::Name(name), Symbol::ArithmeticBlock(arguments)) = (&section[0], &section[1])
-        {
-            // Get function
-            // let name = match &section[0] {
-            //     Symbol::Name(name) => name.clone(),
-            //     _ => panic!()
-            // };
-
-            let function = reference_stack.get_variable(name)?;
-
-            // Get arguments
-            // let arguments = match &section[1] {
-            //     Symbol::ArithmeticBlock(symbols) => symbols,
-            //     _ => panic!()
-            // };
-
-            let mut i: usize = 0;
-
-            let mut argument_list = Vec::new();
-
-            while i < arguments.len() {
-                argument_list.push(
-                    handle_arithmetic_section(
-                        memory_managers,
-                        reference_stack,
-                        &[arguments[i].clone()],
-                        None,
-                        true,
-                    )?
-                    .unwrap(),
-                );
+        match symbol {
+            //? Prefix operators e.g. '!a', '- a'
+            Symbol::Operator(operator @ (Operator::Not | Operator::Subtract)) => Ok(
+                Expression::Prefix(*operator, Box::new(self.parse_operand()?)),
+            ),
+            Symbol::Name(name, span) => match self.peek() {
+                //? Function call
+                Some(Symbol::ArithmeticBlock(arguments, _)) => {
+                    self.next();
+                    let arguments = arguments
+                        .split(|s| matches!(s, Symbol::Punctuation(Punctuation::ListSeparator)))
+                        .collect::<Vec<_>>();
 
-                i += 1;
+                    // 'f()' splits into one empty argument
+                    if arguments.len() == 1 && arguments[0].is_empty() {
+                        return Ok(Expression::Call(name, *span, Vec::new()));
+                    }
 
-                if i < arguments.len() {
-                    #[allow(unreachable_patterns)]
-                    match arguments[i] {
-                        Symbol::Punctuation(Punctuation::ListSeparator) => {}
-                        _ => {
-                            return Err(
+                    let mut argument_list = Vec::with_capacity(arguments.len());
+                    for argument in arguments {
+                        if argument.is_empty() {
+                            return Err(CompileError::Syntax(
                                 "Arguments must be formatted ([ARGUMENT] , [ARGUMENT] , [...]"
                                     .to_string(),
-                            )
+                            ));
                         }
+                        argument_list.push(Self::parse(argument)?);
                     }
+                    Ok(Expression::Call(name, *span, argument_list))
                 }
-                i += 1
-            }
+                //? Indexing
+                Some(Symbol::Indexer(index, _)) => {
+                    self.next();
 
-            return match to_overwrite {
-                Some(to_overwrite) => {
-                    match function.call(
-                        memory_managers,
-                        argument_list.iter().collect(),
-                        Some(to_overwrite),
-                    ) {
-                        Err(e) => Err(e),
-                        Ok(_) => Ok(None),
-                    }
-                }
-                None => {
-                    return if must_evaluate {
-                        // Call function with created destination
-                        let return_type = match function.get_return_type() {
-                            Err(e) => return Err(e),
-                            Ok(value) => get_type(&value, memory_managers)?,
-                        };
-
-                        match function.call(
-                            memory_managers,
-                            argument_list.iter().collect(),
-                            Some(&return_type),
-                        ) {
-                            Err(e) => Err(e),
-                            Ok(_) => Ok(Some(return_type)),
+                    //? Slicing e.g. 'a[start, end]'
+                    if let Symbol::ArithmeticBlock(symbols, _) = index.as_ref() {
+                        let bounds = symbols
+                            .split(|s| matches!(s, Symbol::Punctuation(Punctuation::ListSeparator)))
+                            .collect::<Vec<_>>();
+                        if bounds.len() == 2 {
+                            return Ok(Expression::Slice(
+                                name,
+                                *span,
+                                Box::new(Self::parse(bounds[0])?),
+                                Box::new(Self::parse(bounds[1])?),
+                            ));
+                        } else if bounds.len() > 2 {
+                            return Err(CompileError::Syntax(
+                                "Slices must be formatted [NAME][[START] , [END]]".to_string(),
+                            ));
                         }
-                    } else {
-                        // Call function without handling return
-                        match function.call(memory_managers, argument_list.iter().collect(), None) {
-                            Err(e) => Err(e),
-                            Ok(_) => Ok(None),
-                        }
-                    };
+                    }
+
+                    Ok(Expression::Index(
+                        name,
+                        *span,
+                        Box::new(Self::parse(std::slice::from_ref(index.as_ref()))?),
+                    ))
                 }
-            };
+                _ => Ok(Expression::Name(name, *span)),
+            },
+            Symbol::Literal(literal, span) => Ok(Expression::Literal(literal, *span)),
+            Symbol::ArithmeticBlock(symbols, _) => Self::parse(symbols),
+            Symbol::Indexer(..) => Err(CompileError::Syntax(
+                "Only a Name can be indexed".to_string(),
+            )),
+            symbol => Err(CompileError::Syntax(format!(
+                "Expected a value but found {}",
+                symbol
+            ))),
         }
-        //? Indexing
-        else if matches!(section[1], Symbol::Indexer(_)) {
-            // Get variable
-            let to_index = match &section[0] {
-                Symbol::Name(name) => reference_stack.get_variable(name)?,
-                _ => return Err("Only a Name can be indexed".to_string()),
-            };
+    }
+}
 
-            // Index
-            #[allow(unused_assignments)]
-            let mut type_holder = None;
-            let index = match &section[1] {
-                Symbol::Indexer(symbol) => match symbol.as_ref() {
-                    Symbol::Name(name) => reference_stack.get_variable(name)?,
-                    Symbol::Literal(literal) => {
-                        match get_type_from_literal(literal, memory_managers) {
-                            Err(e) => return Err(e),
-                            Ok(value) => {
-                                value.static_assign_literal(memory_managers, literal)?;
-                                type_holder = Some(value);
-                                type_holder.as_ref().unwrap()
-                            }
-                        }
-                    }
-                    _ => return Err("Name can only be indexed by a Name or a Literal".to_string()),
-                },
-                _ => panic!(),
-            };
+/// Result of evaluating an expression
+enum Value<'a> {
+    /// An existing variable - nothing was emitted
+    Variable(&'a Type),
+    /// A newly created `Type` holding the result
+    Temporary(Type),
+}
 
-            if let Some(o) = to_overwrite {
-                return match to_index.get_indexed(memory_managers, index, o) {
-                    Err(e) => return Err(e),
-                    Ok(_) => Ok(None),
-                };
-            } else {
-                let return_type = get_type(&to_index.get_type(), memory_managers)?;
+impl Value<'_> {
+    fn get(&self) -> &Type {
+        match self {
+            Value::Variable(variable) => variable,
+            Value::Temporary(temporary) => temporary,
+        }
+    }
+}
 
-                return match to_index.get_indexed(memory_managers, index, &return_type) {
-                    Err(e) => return Err(e),
-                    Ok(_) => Ok(Some(return_type)),
-                };
+/// Checks that a result can be written into `destination`
+fn check_destination(destination: &Type, result_type: TypeSymbol) -> Result<(), CompileError> {
+    if destination.get_type() != result_type {
+        return Err(CompileError::TypeMismatch {
+            expected: destination.get_type(),
+            found: result_type,
+        });
+    }
+    Ok(())
+}
+
+/// Evaluates an expression
+///
+/// # Arguments
+/// * `destination` - Type to write result to
+/// * `type_hint` - Type literals should take if they have no other context
+/// * `must_evaluate` - Whether a top level function call must return a value
+///
+/// # Returns
+/// * `Ok(None)` if `destination` is `Some` or a function call is not evaluated
+/// * `Ok(Some(value))` otherwise
+fn evaluate<'a>(
+    expression: &Expression,
+    memory_managers: &mut MemoryManagers,
+    reference_stack: &'a ReferenceStack,
+    destination: Option<&Type>,
+    type_hint: Option<TypeSymbol>,
+    must_evaluate: bool,
+) -> Result<Option<Value<'a>>, CompileError> {
+    match expression {
+        Expression::Name(name, span) => {
+            let variable = reference_stack
+                .get_variable(name)
+                .map_err(|e| e.with_span(*span))?;
+            match destination {
+                Some(destination) => {
+                    destination.assign_clone(memory_managers, variable)?;
+                    Ok(None)
+                }
+                None => Ok(Some(Value::Variable(variable))),
             }
         }
-        //? Prefix operator e.g. '!a'
-        else {
-            // Get operator
-            let operator = match section[0] {
-                Symbol::Operator(op) => op,
-                _ => return Err(get_formatting_error()),
-            };
+        Expression::Literal(literal, span) => {
+            evaluate_literal(literal, memory_managers, destination, type_hint)
+                .map_err(|e| e.with_span(*span))
+        }
+        Expression::Call(name, span, arguments) => {
+            if let Ok(struct_type) = reference_stack.get_struct(name) {
+                return construct_struct(
+                    struct_type,
+                    arguments,
+                    memory_managers,
+                    reference_stack,
+                    destination,
+                );
+            }
+
+            let function = reference_stack
+                .get_variable(name)
+                .map_err(|e| e.with_span(*span))?;
+            let parameter_types = function
+                .get_parameter_types()
+                .map_err(|e| e.with_span(*span))?;
+
+            // Arguments take the type of their parameter where possible
+            let mut argument_list = Vec::with_capacity(arguments.len());
+            for (index, argument) in arguments.iter().enumerate() {
+                argument_list.push(evaluate_value(
+                    argument,
+                    memory_managers,
+                    reference_stack,
+                    parameter_types.get(index).copied(),
+                )?);
+            }
+            let argument_list = argument_list.iter().map(|a| a.get()).collect();
 
-            // Get operand
-            let mut _lhs_holder = None;
-            let lhs = match &section[1] {
-                Symbol::Name(name) => reference_stack.get_variable(name)?,
-                Symbol::Literal(literal) => {
-                    let object = get_type_from_literal(literal, memory_managers)?;
-                    object.static_assign_literal(memory_managers, literal)?;
-                    _lhs_holder = Some(object);
-                    _lhs_holder.as_ref().unwrap()
+            match destination {
+                Some(destination) => {
+                    function.call(memory_managers, argument_list, Some(destination))?;
+                    Ok(None)
                 }
-                Symbol::ArithmeticBlock(symbols) => {
-                    match handle_arithmetic_section(
+                None if must_evaluate => {
+                    // Call function with created destination
+                    let return_value = get_scoped_type(
+                        &function.get_return_type()?,
                         memory_managers,
                         reference_stack,
-                        symbols,
-                        None,
-                        true,
-                    ) {
-                        Err(e) => return Err(e),
-                        Ok(object) => {
-                            _lhs_holder = Some(object.unwrap());
-                            _lhs_holder.as_ref().unwrap()
-                        }
-                    }
+                    )?;
+                    function.call(memory_managers, argument_list, Some(&return_value))?;
+                    Ok(Some(Value::Temporary(return_value)))
                 }
-                _ => {
-                    return Err(
-                        "Operand must be a Name, Literal or an operation within brackets"
-                            .to_string(),
-                    )
+                None => {
+                    // Call function without handling return
+                    function.call(memory_managers, argument_list, None)?;
+                    Ok(None)
                 }
-            };
-
-            // Return
-            if to_overwrite.is_none() {
-                let result_type = lhs.get_operation_return_type(&operator, None)?;
-
-                let result = get_type(&result_type, memory_managers)?;
-                lhs.operate(memory_managers, operator, None, &result)?;
+            }
+        }
+        Expression::Index(name, span, index) => {
+            let to_index = reference_stack
+                .get_variable(name)
+                .map_err(|e| e.with_span(*span))?;
+            let index = evaluate_value(
+                index,
+                memory_managers,
+                reference_stack,
+                Some(TypeSymbol::Pointer),
+            )?;
 
-                return Ok(Some(result));
-            } else {
-                lhs.operate(memory_managers, operator, None, to_overwrite.unwrap())?;
+            match destination {
+                Some(destination) => {
+                    to_index.get_indexed(memory_managers, index.get(), destination)?;
+                    Ok(None)
+                }
+                None => {
+                    let result = get_type(&to_index.get_element_type(), memory_managers)?;
+                    to_index.get_indexed(memory_managers, index.get(), &result)?;
+                    Ok(Some(Value::Temporary(result)))
+                }
+            }
+        }
+        Expression::Slice(name, span, start, end) => {
+            let to_slice = reference_stack
+                .get_variable(name)
+                .map_err(|e| e.with_span(*span))?;
+            let start = evaluate_value(
+                start,
+                memory_managers,
+                reference_stack,
+                Some(TypeSymbol::Pointer),
+            )?;
+            let end = evaluate_value(
+                end,
+                memory_managers,
+                reference_stack,
+                Some(TypeSymbol::Pointer),
+            )?;
 
-                return Ok(None);
+            match destination {
+                Some(destination) => {
+                    to_slice.get_slice(memory_managers, start.get(), end.get(), destination)?;
+                    Ok(None)
+                }
+                None => {
+                    let result = get_type(&to_slice.get_type(), memory_managers)?;
+                    to_slice.get_slice(memory_managers, start.get(), end.get(), &result)?;
+                    Ok(Some(Value::Temporary(result)))
+                }
             }
         }
-    }
-    //? One symbol
-    else {
-        return match &section[0] {
-            // Get type out of name
-            Symbol::Name(name) => match reference_stack.get_variable(name) {
-                Err(e) => return Err(e),
-                Ok(value) => {
-                    if to_overwrite.is_none() {
-                        let object = get_type(&value.get_type(), memory_managers)?;
-                        object.assign_clone(memory_managers, value)?;
-                        Ok(Some(object))
-                    } else {
-                        to_overwrite.unwrap().assign_clone(memory_managers, value)?;
-                        Ok(None)
+        Expression::Prefix(operator, operand) => {
+            //? Negative literals can be created directly
+            if let (Operator::Subtract, Expression::Literal(Literal::Int(integer), _)) =
+                (operator, operand.as_ref())
+            {
+                let negated = match integer.checked_neg() {
+                    Some(negated) => Literal::Int(negated),
+                    None => {
+                        return Err(CompileError::InvalidLiteral(format!(
+                            "Cannot negate {}",
+                            integer
+                        )))
                     }
+                };
+                return evaluate_literal(&negated, memory_managers, destination, type_hint);
+            }
+
+            let operand = evaluate_value(operand, memory_managers, reference_stack, type_hint)?;
+            let operand = operand.get();
+
+            //? Negation is performed as '0 - operand'
+            if *operator == Operator::Subtract {
+                let zero = get_type(&operand.get_type(), memory_managers)?;
+                if zero
+                    .static_assign_literal(memory_managers, &Literal::Int(0))
+                    .is_err()
+                {
+                    return create_op_not_impl_error(operator, operand.get_type(), None);
                 }
-            },
-            // Get type out of literal
-            Symbol::Literal(literal) => {
-                if to_overwrite.is_none() {
-                    let object = get_type_from_literal(literal, memory_managers)?;
-                    object.static_assign_literal(memory_managers, literal)?;
-                    Ok(Some(object))
-                } else {
-                    to_overwrite
-                        .unwrap()
-                        .static_assign_literal(memory_managers, literal)?;
-                    Ok(None)
-                }
+                return operate(
+                    memory_managers,
+                    &zero,
+                    *operator,
+                    Some(operand),
+                    destination,
+                );
             }
-            // Recurse into arithmetic block
-            Symbol::ArithmeticBlock(symbols) => handle_arithmetic_section(
+
+            operate(memory_managers, operand, *operator, None, destination)
+        }
+        Expression::Infix(lhs, operator, rhs) => {
+            // Arithmetic results have the same type as their operands so literals can share the hint
+            let operand_hint = if is_arithmetic(operator) {
+                destination.map(|d| d.get_type()).or(type_hint)
+            } else {
+                None
+            };
+
+            // Evaluate the non-literal side first so a literal can take its type e.g. 'int_value + 1'
+            let (lhs, rhs) = if lhs.is_literal() && !rhs.is_literal() {
+                let rhs = evaluate_value(rhs, memory_managers, reference_stack, operand_hint)?;
+                let lhs = evaluate_value(
+                    lhs,
+                    memory_managers,
+                    reference_stack,
+                    Some(rhs.get().get_type()),
+                )?;
+                (lhs, rhs)
+            } else {
+                let lhs = evaluate_value(lhs, memory_managers, reference_stack, operand_hint)?;
+                let rhs_hint = if rhs.is_literal() {
+                    Some(lhs.get().get_type())
+                } else {
+                    operand_hint
+                };
+                let rhs = evaluate_value(rhs, memory_managers, reference_stack, rhs_hint)?;
+                (lhs, rhs)
+            };
+
+            operate(
                 memory_managers,
-                reference_stack,
-                symbols,
-                to_overwrite,
-                true,
-            ),
-            _ => return Err("Only a name or literal can stand alone".to_string()),
-        };
+                lhs.get(),
+                *operator,
+                Some(rhs.get()),
+                destination,
+            )
+        }
+    }
+}
+
+/// Evaluates an expression that must produce a value
+fn evaluate_value<'a>(
+    expression: &Expression,
+    memory_managers: &mut MemoryManagers,
+    reference_stack: &'a ReferenceStack,
+    type_hint: Option<TypeSymbol>,
+) -> Result<Value<'a>, CompileError> {
+    match evaluate(
+        expression,
+        memory_managers,
+        reference_stack,
+        None,
+        type_hint,
+        true,
+    )? {
+        Some(value) => Ok(value),
+        None => Err(CompileError::NoValue),
+    }
+}
+
+/// Creates a `Type` from a literal or assigns the literal to `destination`
+fn evaluate_literal<'a>(
+    literal: &Literal,
+    memory_managers: &mut MemoryManagers,
+    destination: Option<&Type>,
+    type_hint: Option<TypeSymbol>,
+) -> Result<Option<Value<'a>>, CompileError> {
+    match destination {
+        Some(destination) => {
+            destination.static_assign_literal(memory_managers, literal)?;
+            Ok(None)
+        }
+        None => {
+            let object = get_type_from_literal_with_hint(literal, type_hint, memory_managers)?;
+            object.static_assign_literal(memory_managers, literal)?;
+            Ok(Some(Value::Temporary(object)))
+        }
+    }
+}
+
+/// Performs an operation, writing into `destination` or a new `Type`
+fn operate<'a>(
+    memory_managers: &mut MemoryManagers,
+    lhs: &Type,
+    operator: Operator,
+    rhs: Option<&Type>,
+    destination: Option<&Type>,
+) -> Result<Option<Value<'a>>, CompileError> {
+    let result_type = lhs.get_operation_return_type(&operator, rhs)?;
+
+    match destination {
+        Some(destination) => {
+            check_destination(destination, result_type)?;
+            lhs.operate(memory_managers, operator, rhs, destination)?;
+            Ok(None)
+        }
+        None => {
+            let result = get_type(&result_type, memory_managers)?;
+            lhs.operate(memory_managers, operator, rhs, &result)?;
+            Ok(Some(Value::Temporary(result)))
+        }
+    }
+}
+
+/// Builds a struct from one argument per field, in declaration order
+fn construct_struct<'a>(
+    struct_type: TypeSymbol,
+    arguments: &[Expression],
+    memory_managers: &mut MemoryManagers,
+    reference_stack: &'a ReferenceStack,
+    destination: Option<&Type>,
+) -> Result<Option<Value<'a>>, CompileError> {
+    let result = get_scoped_type(&struct_type, memory_managers, reference_stack)?;
+    let fields = result.get_fields();
+    if arguments.len() != fields.len() {
+        return Err(CompileError::ArgumentCount {
+            expected: fields.len(),
+            found: arguments.len(),
+        });
+    }
+
+    for (argument, field) in arguments.iter().zip(fields) {
+        if field.is_indexed() {
+            return Err(CompileError::UnsupportedOperation(
+                "Array fields can't be set in a struct constructor".to_string(),
+            ));
+        }
+        evaluate(
+            argument,
+            memory_managers,
+            reference_stack,
+            Some(field),
+            Some(field.get_type()),
+            true,
+        )?;
+    }
+
+    match destination {
+        Some(destination) => {
+            destination.assign_clone(memory_managers, &result)?;
+            Ok(None)
+        }
+        None => Ok(Some(Value::Temporary(result))),
+    }
+}
+
+/// Takes an evaluable expression
+///
+/// Expressions can be any length and are evaluated with the following precedence (highest first):
+/// * Prefix `!`, `-`
+/// * `*`, `/`, `%`
+/// * `+`, `-`
+/// * `>`, `<`, `>=`, `<=`
+/// * `==`, `!=`
+/// * `&`
+/// * `|`
+///
+/// # Arguments
+///
+/// * `section` - Expression to evaluate
+/// * `to_overwrite` - Type to write result to
+/// * `must_evaluate` - Whether the expression must evaluate to a type
+///
+/// # Returns
+/// * Returns `Ok(Some(Type))` if to_overwrite is `None`.
+/// * Returns `Ok(None)` if to_overwrite is `Some`,
+pub fn handle_arithmetic_section(
+    memory_managers: &mut MemoryManagers,
+    reference_stack: &ReferenceStack,
+    section: &[Symbol],
+    to_overwrite: Option<&Type>,
+    must_evaluate: bool,
+) -> Result<Option<Type>, CompileError> {
+    let expression = ExpressionParser::parse(section)?;
+
+    let value = evaluate(
+        &expression,
+        memory_managers,
+        reference_stack,
+        to_overwrite,
+        to_overwrite.map(|t| t.get_type()),
+        must_evaluate,
+    )?;
+
+    match value {
+        None => Ok(None),
+        Some(Value::Temporary(result)) => Ok(Some(result)),
+        // Copy variables so the result can't be changed by later assignments to the variable
+        Some(Value::Variable(variable)) => {
+            let object = get_scoped_type(&variable.get_type(), memory_managers, reference_stack)?;
+            object.assign_clone(memory_managers, variable)?;
+            Ok(Some(object))
+        }
     }
 }
@@ -17,10 +17,10 @@ impl LineHandler for CallLine {
             return ProcessingResult::Unmatched;
         }
 
-        if !matches!(line[0], Symbol::Name(_)) {
+        if !matches!(line[0], Symbol::Name(..)) {
             return ProcessingResult::Unmatched;
         }
-        if !matches!(line[1], Symbol::ArithmeticBlock(_)) {
+        if !matches!(line[1], Symbol::ArithmeticBlock(..)) {
             return ProcessingResult::Unmatched;
         }
 
@@ -1,9 +1,12 @@
+use crate::errors::CompileError;
 use crate::processing::blocks::BlockCoordinator;
 use crate::processing::lines::arithmetic::handle_arithmetic_section;
 use crate::processing::lines::LineHandler;
 use crate::processing::processor::{MemoryManagers, ProcessingResult};
-use crate::processing::symbols::Symbol;
-use crate::processing::types::get_type_from_literal;
+use crate::processing::symbols::Symbol::ArithmeticBlock;
+use crate::processing::symbols::{get_symbols_span, Symbol};
+use crate::processing::types::get_type;
+use std::slice;
 
 pub struct IndexedVariableAssignmentLine {}
 
@@ -18,38 +21,31 @@ impl LineHandler for IndexedVariableAssignmentLine {
         }
 
         // Get variable
-        let name = match &line[0] {
-            Symbol::Name(name) => name,
+        let (name, span) = match &line[0] {
+            Symbol::Name(name, span) => (name, *span),
             _ => return ProcessingResult::Unmatched,
         };
 
         // Get index
-        #[allow(unused_assignments)]
-        let mut type_holder = None;
-        let index = match &line[1] {
-            Symbol::Indexer(symbol) => match symbol.as_ref() {
-                Symbol::Name(name) => match block_coordinator.get_variable(name) {
-                    Err(e) => return ProcessingResult::Failure(e),
-                    Ok(value) => value,
-                },
-                Symbol::Literal(literal) => match get_type_from_literal(literal, memory_managers) {
-                    Err(e) => return ProcessingResult::Failure(e),
-                    Ok(value) => {
-                        if let Err(e) = value.static_assign_literal(memory_managers, literal) {
-                            return ProcessingResult::Failure(e);
-                        }
-                        type_holder = Some(value);
-                        type_holder.as_ref().unwrap()
-                    }
-                },
-                _ => return ProcessingResult::Unmatched,
-            },
+        let index_symbol = match &line[1] {
+            Symbol::Indexer(symbol, _) => symbol.as_ref(),
             _ => return ProcessingResult::Unmatched,
         };
 
+        let index = match handle_arithmetic_section(
+            memory_managers,
+            block_coordinator.get_reference_stack(),
+            slice::from_ref(index_symbol),
+            None,
+            true,
+        ) {
+            Err(e) => return ProcessingResult::Failure(e),
+            Ok(value) => value.unwrap(),
+        };
+
         // Get value to be assigned
         let object = match block_coordinator.get_variable(name) {
-            Err(e) => return ProcessingResult::Failure(e),
+            Err(e) => return ProcessingResult::Failure(e.with_span(span)),
             Ok(object) => object,
         };
 
@@ -57,9 +53,9 @@ impl LineHandler for IndexedVariableAssignmentLine {
         let assigner = match &line[2] {
             Symbol::Assigner(assigner) => assigner,
             _ => {
-                return ProcessingResult::Failure(
+                return ProcessingResult::Failure(CompileError::Syntax(
                     "Name and indexer must be followed by assigner".to_string(),
-                )
+                ))
             }
         };
 
@@ -67,21 +63,29 @@ impl LineHandler for IndexedVariableAssignmentLine {
         let mut rhs = Vec::new();
         line[3..].clone_into(&mut rhs);
 
-        let to_evaluate = assigner.get_expanded_equivalent(line[0].clone(), rhs);
+        // Compound assignment operates on the indexed value e.g. 'a[i] += 1' -> 'a[i] = (a[i]) + (1)'
+        let to_evaluate = assigner.get_expanded_equivalent(
+            ArithmeticBlock(line[0..2].to_vec(), get_symbols_span(&line[0..2])),
+            rhs,
+        );
 
-        let result = match handle_arithmetic_section(
+        let result = match get_type(&object.get_type(), memory_managers) {
+            Err(e) => return ProcessingResult::Failure(e),
+            Ok(value) => value,
+        };
+
+        if let Err(e) = handle_arithmetic_section(
             memory_managers,
             block_coordinator.get_reference_stack(),
             &to_evaluate,
-            None,
+            Some(&result),
             true,
         ) {
-            Err(e) => return ProcessingResult::Failure(e),
-            Ok(value) => value.unwrap(),
-        };
+            return ProcessingResult::Failure(e);
+        }
 
         // Write to object
-        if let Err(e) = object.set_indexed(memory_managers, index, &result) {
+        if let Err(e) = object.set_indexed(memory_managers, &index, &result) {
             return ProcessingResult::Failure(e);
         }
 
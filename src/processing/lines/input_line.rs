@@ -1,8 +1,11 @@
+use crate::errors::CompileError;
 use crate::processing::blocks::BlockCoordinator;
 use crate::processing::instructions::input_instruction_15::InputInstruction;
 use crate::processing::lines::LineHandler;
 use crate::processing::processor::{MemoryManagers, ProcessingResult};
 use crate::processing::symbols::{Builtin, Symbol};
+use crate::processing::types::string::STRING_CAPACITY;
+use crate::processing::types::TypeSymbol;
 
 pub struct InputLine {}
 
@@ -22,14 +25,22 @@ impl LineHandler for InputLine {
         };
 
         let t = match &line[1] {
-            Symbol::Name(name) => match block_coordinator.get_variable(name) {
-                Err(e) => return ProcessingResult::Failure(e),
+            Symbol::Name(name, span) => match block_coordinator.get_variable(name) {
+                Err(e) => return ProcessingResult::Failure(e.with_span(*span)),
                 Ok(t) => t,
             },
-            _ => return ProcessingResult::Failure("Input must be followed by a Name".to_string()),
+            _ => {
+                return ProcessingResult::Failure(CompileError::Syntax(
+                    "Input must be followed by a Name".to_string(),
+                ))
+            }
         };
 
-        InputInstruction::new_alloc(memory_managers, t.get_size(), t.get_address());
+        if t.get_type() == TypeSymbol::String {
+            InputInstruction::new_alloc(memory_managers, STRING_CAPACITY, t.get_address(), true);
+        } else {
+            InputInstruction::new_alloc(memory_managers, t.get_size(), t.get_address(), false);
+        }
 
         ProcessingResult::Success
     }
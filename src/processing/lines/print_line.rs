@@ -1,14 +1,25 @@
+use crate::errors::CompileError;
 use crate::processing::blocks::BlockCoordinator;
 use crate::processing::instructions::print_chars_instruction_9::PrintCharsInstruction;
 use crate::processing::instructions::print_instruction_5::PrintInstruction;
+use crate::processing::instructions::print_string_instruction_28::PrintStringInstruction;
 use crate::processing::lines::arithmetic::handle_arithmetic_section;
 use crate::processing::lines::LineHandler;
 use crate::processing::processor::{MemoryManagers, ProcessingResult};
 use crate::processing::symbols::{Builtin, Literal, Symbol, TypeSymbol};
-use crate::processing::types::get_type;
+use crate::processing::types::{get_type, Type};
 
 pub struct PrintLine {}
 
+/// Prints a `Type` as characters. Strings are printed up to their length
+fn print_chars(memory_managers: &mut MemoryManagers, to_print: &Type) {
+    if to_print.get_type() == TypeSymbol::String {
+        PrintStringInstruction::new_alloc(memory_managers, to_print.get_address());
+    } else {
+        PrintCharsInstruction::new_alloc(memory_managers, to_print, to_print.get_len());
+    }
+}
+
 impl LineHandler for PrintLine {
     fn process_line(
         line: &[Symbol],
@@ -24,9 +35,9 @@ impl LineHandler for PrintLine {
                 match builtin {
                     Builtin::Print => {
                         if line.len() == 1 {
-                            return ProcessingResult::Failure(
+                            return ProcessingResult::Failure(CompileError::Syntax(
                                 "'print' must be followed by something to print".to_string(),
-                            );
+                            ));
                         }
                         match handle_arithmetic_section(
                             memory_managers,
@@ -48,13 +59,13 @@ impl LineHandler for PrintLine {
                     }
                     Builtin::PrintChars => {
                         if line.len() == 1 {
-                            return ProcessingResult::Failure(
+                            return ProcessingResult::Failure(CompileError::Syntax(
                                 "'printc' must be followed by something to print".to_string(),
-                            );
+                            ));
                         }
                         //? Special case for string literals to print correctly
-                        if line.len() == 2 && matches!(line[1], Symbol::Literal(_)) {
-                            if let Symbol::Literal(literal) = &line[1] {
+                        if line.len() == 2 && matches!(line[1], Symbol::Literal(..)) {
+                            if let Symbol::Literal(literal, _) = &line[1] {
                                 if let Literal::String(string) = literal {
                                     let mut t =
                                         get_type(&TypeSymbol::Character, memory_managers).unwrap();
@@ -74,19 +85,17 @@ impl LineHandler for PrintLine {
                                 }
                             }
                         }
-                        if line.len() == 2 && matches!(line[1], Symbol::Name(_)) {
+                        if line.len() == 2 && matches!(line[1], Symbol::Name(..)) {
                             match &line[1] {
-                                Symbol::Name(name) => {
+                                Symbol::Name(name, span) => {
                                     let obj = match block_coordinator.get_variable(name) {
-                                        Err(e) => return ProcessingResult::Failure(e),
+                                        Err(e) => {
+                                            return ProcessingResult::Failure(e.with_span(*span))
+                                        }
                                         Ok(value) => value,
                                     };
 
-                                    PrintCharsInstruction::new_alloc(
-                                        memory_managers,
-                                        obj,
-                                        obj.get_len(),
-                                    );
+                                    print_chars(memory_managers, obj);
                                     ProcessingResult::Success
                                 }
                                 _ => panic!(),
@@ -101,11 +110,7 @@ impl LineHandler for PrintLine {
                             ) {
                                 Err(e) => ProcessingResult::Failure(e),
                                 Ok(value) => {
-                                    PrintCharsInstruction::new_alloc(
-                                        memory_managers,
-                                        value.as_ref().unwrap(),
-                                        value.as_ref().unwrap().get_len(),
-                                    );
+                                    print_chars(memory_managers, value.as_ref().unwrap());
                                     ProcessingResult::Success
                                 }
                             }
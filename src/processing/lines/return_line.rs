@@ -0,0 +1,29 @@
+use crate::processing::blocks::BlockCoordinator;
+use crate::processing::lines::LineHandler;
+use crate::processing::processor::{MemoryManagers, ProcessingResult};
+use crate::processing::symbols::Symbol;
+
+/// Handles `return` and `return [VALUE]`. `return = [VALUE]` is handled as an assignment
+pub struct ReturnLine {}
+
+impl LineHandler for ReturnLine {
+    fn process_line(
+        line: &[Symbol],
+        memory_managers: &mut MemoryManagers,
+        block_coordinator: &mut BlockCoordinator,
+    ) -> ProcessingResult {
+        match line.first() {
+            Some(Symbol::Name(name, _)) if name == "return" => {}
+            _ => return ProcessingResult::Unmatched,
+        }
+
+        if let Some(Symbol::Assigner(_)) = line.get(1) {
+            return ProcessingResult::Unmatched;
+        }
+
+        match block_coordinator.return_block_handler(memory_managers, &line[1..]) {
+            Ok(_) => ProcessingResult::Success,
+            Err(e) => ProcessingResult::Failure(e),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::errors::CompileError;
 use crate::processing::blocks::BlockCoordinator;
 use crate::processing::lines::arithmetic::handle_arithmetic_section;
 use crate::processing::lines::LineHandler;
@@ -16,19 +17,23 @@ impl LineHandler for VariableAssignmentLine {
             return ProcessingResult::Unmatched;
         }
 
-        let name = match &line[0] {
-            Symbol::Name(name) => name,
+        let (name, span) = match &line[0] {
+            Symbol::Name(name, span) => (name, *span),
             _ => return ProcessingResult::Unmatched,
         };
 
         let object = match block_coordinator.get_variable(name) {
-            Err(e) => return ProcessingResult::Failure(e),
+            Err(e) => return ProcessingResult::Failure(e.with_span(span)),
             Ok(object) => object,
         };
 
         let assigner = match &line[1] {
             Symbol::Assigner(assigner) => assigner,
-            _ => return ProcessingResult::Failure("Name must be followed by assigner".to_string()),
+            _ => {
+                return ProcessingResult::Failure(CompileError::Syntax(
+                    "Name must be followed by assigner".to_string(),
+                ))
+            }
         };
 
         let mut rhs = Vec::new();
@@ -1,10 +1,11 @@
 use super::LineHandler;
+use crate::errors::CompileError;
 use crate::processing::blocks::BlockCoordinator;
 use crate::processing::lines::arithmetic::handle_arithmetic_section;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::processor::ProcessingResult;
 use crate::processing::symbols::{Assigner, Symbol};
-use crate::processing::types::get_type;
+use crate::processing::types::get_scoped_type;
 
 pub struct VariableInitialisationLine {}
 
@@ -14,42 +15,51 @@ impl LineHandler for VariableInitialisationLine {
         memory_managers: &mut MemoryManagers,
         block_coordinator: &mut BlockCoordinator,
     ) -> ProcessingResult {
-        if line.is_empty() || !matches!(line[0], Symbol::Type(_)) {
-            return ProcessingResult::Unmatched;
-        }
+        //? Type or struct name
+        let type_symbol = match line.first() {
+            Some(Symbol::Type(type_symbol)) => *type_symbol,
+            Some(Symbol::Name(name, _)) if matches!(line.get(1), Some(Symbol::Name(..))) => {
+                match block_coordinator.get_reference_stack().get_struct(name) {
+                    Err(_) => return ProcessingResult::Unmatched,
+                    Ok(type_symbol) => type_symbol,
+                }
+            }
+            _ => return ProcessingResult::Unmatched,
+        };
 
         if line.len() < 4 {
-            return ProcessingResult::Failure(
+            return ProcessingResult::Failure(CompileError::Syntax(
                 "Type must be followed by a Name, '=' and value to initialise a variable"
                     .to_string(),
-            );
+            ));
         }
 
-        let name = match &line[1] {
-            Symbol::Name(name) => name,
+        let (name, span) = match &line[1] {
+            Symbol::Name(name, span) => (name, *span),
             _ => {
-                return ProcessingResult::Failure(
+                return ProcessingResult::Failure(CompileError::Syntax(
                     "Type must be followed by a Name to initialise a variable".to_string(),
-                )
+                ))
             }
         };
 
         match &line[2] {
             Symbol::Assigner(Assigner::Setter) => {}
             _ => {
-                return ProcessingResult::Failure(
+                return ProcessingResult::Failure(CompileError::Syntax(
                     "Type must be followed by a Name, '=' and value to initialise a variable"
                         .to_string(),
-                )
+                ))
             }
         };
 
-        let mut object = match &line[0] {
-            Symbol::Type(type_symbol) => match get_type(type_symbol, memory_managers) {
-                Err(e) => return ProcessingResult::Failure(e),
-                Ok(value) => value,
-            },
-            _ => panic!(),
+        let mut object = match get_scoped_type(
+            &type_symbol,
+            memory_managers,
+            block_coordinator.get_reference_stack(),
+        ) {
+            Err(e) => return ProcessingResult::Failure(e),
+            Ok(value) => value,
         };
 
         if let Err(e) = handle_arithmetic_section(
@@ -67,7 +77,7 @@ impl LineHandler for VariableInitialisationLine {
             .get_reference_stack_mut()
             .register_variable(object, name.clone())
         {
-            return ProcessingResult::Failure(e);
+            return ProcessingResult::Failure(e.with_span(span));
         };
 
         ProcessingResult::Success
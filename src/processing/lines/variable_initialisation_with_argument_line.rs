@@ -1,4 +1,5 @@
 use super::LineHandler;
+use crate::errors::CompileError;
 use crate::processing::blocks::BlockCoordinator;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::processor::ProcessingResult;
@@ -15,43 +16,47 @@ impl LineHandler for VariableInitialisationWithArgumentLine {
     ) -> ProcessingResult {
         if line.is_empty()
             || !matches!(line[0], Symbol::Type(_))
-            || !matches!(line[1], Symbol::Indexer(_))
+            || !matches!(line[1], Symbol::Indexer(..))
         {
             return ProcessingResult::Unmatched;
         }
 
         if line.len() < 5 {
-            return ProcessingResult::Failure(
+            return ProcessingResult::Failure(CompileError::Syntax(
                 "Type must be followed Indexer, Name, '=' and value to initialise a variable"
                     .to_string(),
-            );
+            ));
         }
 
         let indexer = match &line[1] {
-            Symbol::Indexer(symbol) => match symbol.as_ref() {
-                Symbol::Literal(literal) => literal.clone(),
-                _ => return ProcessingResult::Failure("Indexer must contain Literal".to_string()),
+            Symbol::Indexer(symbol, _) => match symbol.as_ref() {
+                Symbol::Literal(literal, _) => literal.clone(),
+                _ => {
+                    return ProcessingResult::Failure(CompileError::Syntax(
+                        "Indexer must contain Literal".to_string(),
+                    ))
+                }
             },
             _ => panic!(),
         };
 
-        let name = match &line[2] {
-            Symbol::Name(name) => name,
+        let (name, span) = match &line[2] {
+            Symbol::Name(name, span) => (name, *span),
             _ => {
-                return ProcessingResult::Failure(
+                return ProcessingResult::Failure(CompileError::Syntax(
                     "Type and initialiser must be followed by a Name to initialise a variable"
                         .to_string(),
-                )
+                ))
             }
         };
 
         match &line[3] {
             Symbol::Assigner(Assigner::Setter) => {}
             _ => {
-                return ProcessingResult::Failure(
+                return ProcessingResult::Failure(CompileError::Syntax(
                     "Type must be followed by a Name, '=' and value to initialise a variable"
                         .to_string(),
-                )
+                ))
             }
         };
 
@@ -64,11 +69,11 @@ impl LineHandler for VariableInitialisationWithArgumentLine {
         };
 
         let value = match &line[4] {
-            Symbol::Literal(literal) => literal.clone(),
+            Symbol::Literal(literal, _) => literal.clone(),
             _ => {
-                return ProcessingResult::Failure(
+                return ProcessingResult::Failure(CompileError::Syntax(
                     "Can only assign Literals to indexed objects must contain Literal".to_string(),
-                )
+                ))
             }
         };
 
@@ -81,7 +86,7 @@ impl LineHandler for VariableInitialisationWithArgumentLine {
             .get_reference_stack_mut()
             .register_variable(object, name.clone())
         {
-            return ProcessingResult::Failure(e);
+            return ProcessingResult::Failure(e.with_span(span));
         };
 
         ProcessingResult::Success
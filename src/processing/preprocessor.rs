@@ -1,34 +1,70 @@
-use crate::errors::create_line_error;
+use crate::errors::{CompileError, Diagnostic, Span};
 use crate::processing::symbols::Symbol::ArithmeticBlock;
 use crate::processing::symbols::{get_all_symbol, Symbol, STRING_DELIMITERS};
 use debugless_unwrap::DebuglessUnwrapErr;
 
-/// Takes a line of code and returns an array of symbols
+/// Takes a line of code and returns an array of symbols along with the span of the whole line
+///
+/// # Arguments
+/// * `offset` - Column `line` starts at. Spans are given relative to this
 #[allow(clippy::single_match)]
-pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
+pub fn get_symbols_from_line(line: &str, offset: usize) -> Result<(Vec<Symbol>, Span), Diagnostic> {
     let mut symbol_line = Vec::new();
+    // Column after the last symbol
+    let mut symbols_end = offset;
 
     let mut buffer = String::new();
+    // Column of the first character in the buffer
+    let mut buffer_start = offset;
     let mut in_string = false;
     let mut bracket_depth = 0;
+    let mut bracket_start = offset;
     let mut in_indexer = false;
     let mut indexing_start: usize = 0;
+    let mut indexer_start = offset;
+
+    fn syntax_error(message: &str, span: Span) -> Diagnostic {
+        Diagnostic::error(
+            CompileError::UnbalancedDelimiter(message.to_string()),
+            0,
+            span,
+        )
+    }
 
-    fn process_buffer(buffer: &mut String, symbol_line: &mut Vec<Symbol>) -> Result<(), String> {
+    fn process_buffer(
+        buffer: &mut String,
+        buffer_start: usize,
+        symbol_line: &mut Vec<Symbol>,
+        symbols_end: &mut usize,
+    ) -> Result<(), Diagnostic> {
         if buffer.is_empty() {
             return Ok(());
         }
 
+        let span = Span::new(buffer_start, buffer_start + buffer.chars().count());
         let symbol = get_all_symbol(buffer);
         if symbol.is_none() {
-            return Err(format!("Symbol '{}' not found", buffer));
+            //? Split prefix operators from their operand e.g. '!a' -> '!' 'a'
+            if buffer.len() > 1 && (buffer.starts_with('!') || buffer.starts_with('-')) {
+                let mut operand = buffer.split_off(1);
+                process_buffer(buffer, buffer_start, symbol_line, symbols_end)?;
+                return process_buffer(&mut operand, buffer_start + 1, symbol_line, symbols_end);
+            }
+            return Err(Diagnostic::error(
+                CompileError::UnknownSymbol(buffer.clone()),
+                0,
+                span,
+            ));
         }
-        symbol_line.push(symbol.unwrap());
+        symbol_line.push(symbol.unwrap().with_span(span));
+        *symbols_end = span.end;
         buffer.clear();
         Ok(())
     }
 
-    for c in line.chars() {
+    for (column, c) in line.chars().enumerate() {
+        let column = column + offset;
+
         //? Comments
         if c == '#' && !in_string {
             break;
@@ -37,7 +73,12 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
         if bracket_depth == 0 && !in_string {
             //? Process buffer and ignore c
             match match c {
-                ' ' => Some(process_buffer(&mut buffer, &mut symbol_line)),
+                ' ' => Some(process_buffer(
+                    &mut buffer,
+                    buffer_start,
+                    &mut symbol_line,
+                    &mut symbols_end,
+                )),
                 _ => None,
             } {
                 Some(value) => match value {
@@ -49,7 +90,12 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
 
             //? Process buffer and then treat c normally
             match match c {
-                '(' => Some(process_buffer(&mut buffer, &mut symbol_line)),
+                '(' => Some(process_buffer(
+                    &mut buffer,
+                    buffer_start,
+                    &mut symbol_line,
+                    &mut symbols_end,
+                )),
                 _ => None,
             } {
                 Some(value) => value?,
@@ -79,12 +125,22 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
             //? Process character alone
             match match c {
                 ',' => {
-                    let r = process_buffer(&mut buffer, &mut symbol_line);
+                    let r = process_buffer(
+                        &mut buffer,
+                        buffer_start,
+                        &mut symbol_line,
+                        &mut symbols_end,
+                    );
                     if r.is_err() {
                         return Err(r.debugless_unwrap_err());
                     }
                     buffer.push(c);
-                    Some(process_buffer(&mut buffer, &mut symbol_line))
+                    Some(process_buffer(
+                        &mut buffer,
+                        column,
+                        &mut symbol_line,
+                        &mut symbols_end,
+                    ))
                 }
                 _ => None,
             } {
@@ -102,16 +158,19 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
 
             match bracket_depth {
                 0 => {
-                    symbol_line.push(match get_symbols_from_line(buffer.as_str()) {
-                        Ok(symbols) => ArithmeticBlock(symbols),
-                        Err(e) => return Err(e),
-                    });
+                    let (symbols, _) = get_symbols_from_line(buffer.as_str(), bracket_start + 1)?;
+                    symbol_line.push(ArithmeticBlock(
+                        symbols,
+                        Span::new(bracket_start, column + 1),
+                    ));
+                    symbols_end = column + 1;
                     buffer.clear();
                 }
                 i32::MIN..=-1 => {
-                    return Err(
-                        "Closing bracket found with no corresponding opening bracket".to_string(),
-                    );
+                    return Err(syntax_error(
+                        "Closing bracket found with no corresponding opening bracket",
+                        Span::new(column, column + 1),
+                    ));
                 }
                 _ => {
                     buffer.push(c);
@@ -130,75 +189,119 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
         if c == '(' && !in_string {
             if bracket_depth != 0 {
                 buffer.push(c);
+            } else {
+                bracket_start = column;
             }
             bracket_depth += 1;
             continue;
         }
 
         //? End indexer
-        if c == ']' && !in_string {
+        if c == ']' && !in_string && bracket_depth == 0 {
             if !buffer.is_empty() {
-                process_buffer(&mut buffer, &mut symbol_line)?;
+                process_buffer(
+                    &mut buffer,
+                    buffer_start,
+                    &mut symbol_line,
+                    &mut symbols_end,
+                )?;
             }
+            let indexer_span = Span::new(indexer_start, column + 1);
             if !in_indexer {
-                return Err(
-                    "Closing indexer bracket found with no corresponding opening bracket"
-                        .to_string(),
-                );
-            }
-            if symbol_line.len() - indexing_start > 1 {
-                return Err("Indexers may only contain one symbol".to_string());
+                return Err(syntax_error(
+                    "Closing indexer bracket found with no corresponding opening bracket",
+                    Span::new(column, column + 1),
+                ));
             }
             if symbol_line.len() - indexing_start < 1 {
-                return Err("Indexer must contain a symbol".to_string());
+                return Err(syntax_error("Indexer must contain a symbol", indexer_span));
             }
-            let symbol = symbol_line.pop().unwrap();
-            symbol_line.push(Symbol::Indexer(Box::new(symbol)));
+            //? Indexers containing an expression are treated as being in brackets
+            let symbol = if symbol_line.len() - indexing_start > 1 {
+                ArithmeticBlock(
+                    symbol_line.split_off(indexing_start),
+                    Span::new(indexer_start + 1, column),
+                )
+            } else {
+                symbol_line.pop().unwrap()
+            };
+            symbol_line.push(Symbol::Indexer(Box::new(symbol), indexer_span));
+            symbols_end = column + 1;
             in_indexer = false;
             continue;
         }
 
         //? Start indexer
-        if c == '[' && !in_string {
+        if c == '[' && !in_string && bracket_depth == 0 {
             if !buffer.is_empty() {
-                process_buffer(&mut buffer, &mut symbol_line)?;
+                process_buffer(
+                    &mut buffer,
+                    buffer_start,
+                    &mut symbol_line,
+                    &mut symbols_end,
+                )?;
             }
             if in_indexer {
-                return Err("Recursive indexing not permitted".to_string());
+                return Err(syntax_error(
+                    "Recursive indexing not permitted",
+                    Span::new(column, column + 1),
+                ));
             }
             indexing_start = symbol_line.len();
+            indexer_start = column;
             in_indexer = true;
             continue;
         }
 
+        if buffer.is_empty() {
+            buffer_start = column;
+        }
         buffer.push(c);
     }
 
+    let line_end = offset + line.chars().count();
+
     if in_string {
-        return Err("Unclosed string".to_string());
+        return Err(syntax_error(
+            "Unclosed string",
+            Span::new(buffer_start, line_end),
+        ));
     }
 
     if bracket_depth != 0 {
-        return Err("Unclosed brackets".to_string());
+        return Err(syntax_error(
+            "Unclosed brackets",
+            Span::new(bracket_start, line_end),
+        ));
     }
 
-    //? Push remaining data
-    if !buffer.is_empty() {
-        let symbol = get_all_symbol(&buffer);
-        if symbol.is_none() {
-            return Err(format!("Symbol '{}' not found", buffer));
-        }
-        symbol_line.push(symbol.unwrap());
+    if in_indexer {
+        return Err(syntax_error(
+            "Unclosed indexer",
+            Span::new(indexer_start, line_end),
+        ));
     }
 
-    Ok(symbol_line)
+    //? Push remaining data
+    process_buffer(
+        &mut buffer,
+        buffer_start,
+        &mut symbol_line,
+        &mut symbols_end,
+    )?;
+
+    Ok((symbol_line, Span::new(offset, symbols_end)))
 }
 
 /// Takes code as an input
 ///
-/// Returns `Vec<indentation, symbol line>`
-pub fn convert_to_symbols(data: String) -> Result<Vec<(usize, Vec<Symbol>)>, String> {
+/// Returns `Vec<indentation, symbol line, line span>`
+///
+/// Lines that fail to convert are left empty so that every error in `data` can be returned
+#[allow(clippy::type_complexity)]
+pub fn convert_to_symbols(data: &str) -> Result<Vec<(usize, Vec<Symbol>, Span)>, Vec<Diagnostic>> {
     let mut output = Vec::new();
+    let mut errors = Vec::new();
 
     for (line_index, line) in data.lines().enumerate() {
         //? Count indentation
@@ -215,19 +318,32 @@ pub fn convert_to_symbols(data: String) -> Result<Vec<(usize, Vec<Symbol>)>, Str
             indentation_char_count += 1;
         }
         if indentation_count % 4 != 0 {
-            return create_line_error(
-                "Indentation must be a multiple of 4 spaces or single tabs".to_string(),
-                line_index + 1,
-            );
+            errors.push(Diagnostic::error(
+                CompileError::BadIndentation(
+                    "Indentation must be a multiple of 4 spaces or single tabs".to_string(),
+                ),
+                line_index,
+                Span::new(0, indentation_char_count),
+            ));
+            output.push((0, Vec::new(), Span::default()));
+            continue;
         }
 
         //? Get symbols
-        let symbols = match get_symbols_from_line(&line[indentation_char_count..]) {
-            Err(e) => return create_line_error(e, line_index),
-            Ok(symbols) => symbols,
-        };
-        output.push((indentation_count / 4, symbols));
+        let (symbols, span) =
+            match get_symbols_from_line(&line[indentation_char_count..], indentation_char_count) {
+                Err(mut e) => {
+                    e.line = line_index;
+                    errors.push(e);
+                    (Vec::new(), Span::default())
+                }
+                Ok(value) => value,
+            };
+        output.push((indentation_count / 4, symbols, span));
     }
 
+    if !errors.is_empty() {
+        return Err(errors);
+    }
     Ok(output)
 }
@@ -1,4 +1,6 @@
-use crate::errors::create_line_error;
+use crate::compiled_file::CompiledFile;
+use crate::debug_info::DebugInfo;
+use crate::errors::{CompileError, Diagnostic, LoadError, RuntimeError, Span};
 use crate::memory_manager::MemoryManager;
 use crate::processing::blocks::BlockCoordinator;
 use crate::processing::lines::break_continue_line::BreakContinueLine;
@@ -7,23 +9,25 @@ use crate::processing::lines::function_line::FunctionLine;
 use crate::processing::lines::if_line::IfLine;
 use crate::processing::lines::indexed_variable_assignment_line::IndexedVariableAssignmentLine;
 use crate::processing::lines::input_line::InputLine;
+use crate::processing::lines::loop_line::LoopLine;
 use crate::processing::lines::print_line::PrintLine;
+use crate::processing::lines::return_line::ReturnLine;
+use crate::processing::lines::struct_line::StructLine;
 use crate::processing::lines::variable_assignment_line::VariableAssignmentLine;
 use crate::processing::lines::variable_initialisation_line::VariableInitialisationLine;
 use crate::processing::lines::variable_initialisation_with_argument_line::VariableInitialisationWithArgumentLine;
 use crate::processing::lines::while_line::WhileLine;
 use crate::processing::lines::LineHandler;
-use crate::processing::symbols::Symbol;
-use crate::util::get_usize;
+use crate::processing::symbols::{Block, Symbol};
+use crate::status_println;
+use crate::util::POINTER_SIZE;
 use num_format::{Locale, ToFormattedString};
 use std::fs;
-use std::io::Write;
-use std::mem::size_of;
 
 pub enum ProcessingResult {
     Success,
     Unmatched,
-    Failure(String),
+    Failure(CompileError),
 }
 
 impl ProcessingResult {
@@ -45,7 +49,7 @@ impl ProcessingResult {
         matches!(self, Self::Unmatched)
     }
 
-    pub fn get_error(self) -> String {
+    pub fn get_error(self) -> CompileError {
         match self {
             Self::Failure(e) => e,
             _ => panic!("Attempted to get error where there was none!"),
@@ -59,24 +63,40 @@ pub struct MemoryManagers {
 }
 
 impl MemoryManagers {
-    /// Saves memory data to an encoded file that can be loaded by `load_from_compiled`.
-    /// # Save file format
-    /// * Size of variable memory (`usize`)
-    /// * Variable memory
-    /// * Program memory
-    pub fn save_to_compiled(&self, name: String) {
-        let mut to_save = Vec::new();
-        to_save.append(&mut Vec::from(
-            self.variable_memory.get_position().to_le_bytes(),
-        ));
-        to_save.extend(&self.variable_memory.memory);
-        to_save.extend(&self.program_memory.memory);
-
-        let name = name + format!(" - {}.cwhy", (usize::BITS as usize)).as_str();
-
-        println!(
+    /// Reads a `usize` operand at `pointer` in program memory and moves `pointer` past it
+    pub fn read_operand(&self, pointer: &mut usize) -> Result<usize, RuntimeError> {
+        let value = self
+            .program_memory
+            .read_usize(*pointer)
+            .map_err(|_| RuntimeError::UnexpectedEndOfProgram { address: *pointer })?;
+        *pointer += POINTER_SIZE;
+        Ok(value)
+    }
+
+    /// Reads a variable address operand at `pointer` in program memory, resolving it if it is
+    /// relative to the current stack frame
+    pub fn read_variable_address(&self, pointer: &mut usize) -> Result<usize, RuntimeError> {
+        Ok(self.variable_memory.resolve(self.read_operand(pointer)?))
+    }
+
+    /// Saves memory data and optional debug info to a `.cwhy` file at `path` that can be loaded by
+    /// `load_from_compiled`. See `CompiledFile` for the file format
+    pub fn save_to_compiled(
+        &self,
+        path: &str,
+        debug_info: Option<&DebugInfo>,
+    ) -> Result<(), std::io::Error> {
+        let to_save = CompiledFile {
+            variable_memory: self.variable_memory.memory.clone(),
+            program_memory: self.program_memory.memory.clone(),
+            debug_info: debug_info.map(|d| d.encode()),
+        }
+        .encode();
+
+        status_println!(
+            normal,
             "Saving compiled data '{}' [{} bytes - {{{}:{}}}]",
-            &name,
+            path,
             to_save.len().to_formatted_string(&Locale::en),
             self.variable_memory
                 .get_position()
@@ -86,65 +106,35 @@ impl MemoryManagers {
                 .to_formatted_string(&Locale::en)
         );
 
-        let file = fs::OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(name);
-
-        if file.is_err() {
-            println!("Failed to open file - {}", file.unwrap_err());
-            return;
-        }
-
-        let mut file = file.unwrap();
-        let r = file.write_all(&to_save);
-        if r.is_err() {
-            println!("Failed to write to file - {}", r.unwrap_err())
-        }
+        fs::write(path, to_save)
     }
 
-    /// Loads memory data from an encoded file that can be created by `save_from_compiled`.
-    /// # Save file format
-    /// * Size of variable memory (`usize`)
-    /// * Variable memory
-    /// * Program memory
-    pub fn load_from_compiled(path: String) -> Result<Self, String> {
-        println!("Loading precompiled data from file '{}'", &path);
-
-        let data = match fs::read(path) {
-            Err(e) => return Err(e.to_string()),
-            Ok(value) => value,
-        };
-
-        let variable_memory_length = get_usize(&0, &data);
-        let mut variable_memory = Vec::with_capacity(variable_memory_length);
-        let mut program_memory =
-            Vec::with_capacity(data.len() - variable_memory_length - size_of::<usize>());
+    /// Loads memory data from a file created by `save_to_compiled`, rejecting files that are
+    /// corrupt or were compiled for an incompatible platform
+    pub fn load_from_compiled(path: String) -> Result<Self, LoadError> {
+        Ok(Self::load_from_compiled_with_debug_info(path)?.0)
+    }
 
-        for i in data
-            .iter()
-            .skip(size_of::<usize>())
-            .take(variable_memory_length)
-        {
-            variable_memory.push(*i);
-        }
+    /// Loads memory data and debug info, if the file has any, from a file created by
+    /// `save_to_compiled`
+    pub fn load_from_compiled_with_debug_info(
+        path: String,
+    ) -> Result<(Self, Option<DebugInfo>), LoadError> {
+        status_println!(normal, "Loading precompiled data from file '{}'", &path);
 
-        for i in data
-            .iter()
-            .skip(size_of::<usize>() + variable_memory_length)
-        {
-            program_memory.push(*i);
-        }
+        let file = CompiledFile::decode(&fs::read(path)?)?;
+        let debug_info = match file.debug_info {
+            Some(data) => Some(DebugInfo::decode(&data)?),
+            None => None,
+        };
 
-        Ok(Self {
-            variable_memory: MemoryManager {
-                memory: variable_memory,
+        Ok((
+            Self {
+                variable_memory: MemoryManager::from_vec(file.variable_memory),
+                program_memory: MemoryManager::from_vec(file.program_memory),
             },
-            program_memory: MemoryManager {
-                memory: program_memory,
-            },
-        })
+            debug_info,
+        ))
     }
 }
 
@@ -158,16 +148,42 @@ macro_rules! process_line {
     };
 }
 
-/// Takes symbol lines as an input and outputs compiled memory
-pub fn process_symbols(symbols: Vec<(usize, Vec<Symbol>)>) -> Result<MemoryManagers, String> {
+/// Whether a line starts a block e.g. 'if', 'fn' or 'struct'
+fn opens_block(symbol_line: &[Symbol]) -> bool {
+    matches!(symbol_line[0], Symbol::Block(_))
+}
+
+/// Whether a line continues the previous block rather than starting a new one e.g. 'else'
+fn continues_block(symbol_line: &[Symbol]) -> bool {
+    matches!(
+        symbol_line[0],
+        Symbol::Block(Block::Elif) | Symbol::Block(Block::Else)
+    )
+}
+
+/// Takes symbol lines as an input and outputs compiled memory along with debug info mapping it
+/// back to the source lines
+///
+/// Lines that fail are skipped along with the block they start so that every error can be
+/// returned
+pub fn process_symbols(
+    symbols: Vec<(usize, Vec<Symbol>, Span)>,
+) -> Result<(MemoryManagers, DebugInfo), Vec<Diagnostic>> {
     let mut memory_managers = MemoryManagers {
         program_memory: MemoryManager::new(),
         variable_memory: MemoryManager::new(),
     };
 
     let mut block_coordinator = BlockCoordinator::new();
+    let mut debug_info = DebugInfo::new();
 
-    let line_count = symbols.len();
+    let mut errors = Vec::new();
+    // Indentation of the last block that failed to start, or of the lines before an over-indented
+    // line. Lines indented further are skipped
+    let mut failed_indentation: Option<usize> = None;
+    // Line index and span of the line that started each open block. Errors from closing a
+    // block are reported there
+    let mut block_starts: Vec<(usize, Span)> = Vec::new();
 
     'line_iterator: for (line_index, line) in symbols.into_iter().enumerate() {
         //? Skip empty lines
@@ -177,35 +193,79 @@ pub fn process_symbols(symbols: Vec<(usize, Vec<Symbol>)>) -> Result<MemoryManag
 
         let indentation = line.0;
         let symbol_line = line.1;
+        let line_span = line.2;
+
+        //? Skip the body and any 'elif' / 'else' of a block that failed
+        if let Some(failed) = failed_indentation {
+            if indentation > failed || (indentation == failed && continues_block(&symbol_line)) {
+                continue;
+            }
+            failed_indentation = None;
+        }
 
         //? Error if indentation is skipped
         if indentation > block_coordinator.get_indentation() {
-            return create_line_error("Indentation to high".to_string(), line_index);
+            errors.push(Diagnostic::error(
+                CompileError::BadIndentation("Indentation to high".to_string()),
+                line_index,
+                Span::new(0, line_span.start),
+            ));
+            failed_indentation = Some(block_coordinator.get_indentation());
+            continue;
         }
 
         //? Exit blocks until block indentation matches code indentation
+        block_coordinator
+            .get_reference_stack_mut()
+            .set_program_position(memory_managers.program_memory.get_position());
         while block_coordinator.get_indentation() >= 1
             && indentation < block_coordinator.get_indentation()
         {
             if block_coordinator.get_indentation() >= 2
                 && indentation <= block_coordinator.get_indentation() - 2
             {
+                let (start_index, start_span) = block_starts.pop().unwrap();
                 let result = block_coordinator.force_exit_block_handler(&mut memory_managers);
                 if let Err(e) = result {
-                    return create_line_error(e, line_index);
+                    errors.push(Diagnostic::error(e, start_index, start_span));
                 }
             } else {
-                let result =
-                    block_coordinator.exit_block_handler(&mut memory_managers, &symbol_line);
-                if let Err(e) = result {
-                    return create_line_error(e, line_count);
-                }
-                if !result.unwrap() {
-                    continue 'line_iterator;
+                let (start_index, start_span) = *block_starts.last().unwrap();
+                match block_coordinator.exit_block_handler(&mut memory_managers, &symbol_line) {
+                    Err(e) => {
+                        block_starts.pop();
+                        //? A failed 'elif' / 'else' is reported on its own line and takes its
+                        //? body with it
+                        if continues_block(&symbol_line) {
+                            errors.push(Diagnostic::error(e, line_index, line_span));
+                            failed_indentation = Some(indentation);
+                            continue 'line_iterator;
+                        }
+                        errors.push(Diagnostic::error(e, start_index, start_span));
+                    }
+                    Ok(false) => continue 'line_iterator,
+                    Ok(true) => {
+                        block_starts.pop();
+                    }
                 }
             }
         }
 
+        debug_info.add_line(memory_managers.program_memory.get_position(), line_index);
+        block_coordinator
+            .get_reference_stack_mut()
+            .set_program_position(memory_managers.program_memory.get_position());
+
+        //? Let the current block process the line e.g. struct fields
+        match block_coordinator.line_block_handler(&mut memory_managers, &symbol_line) {
+            Err(e) => {
+                errors.push(Diagnostic::error(e, line_index, line_span));
+                continue;
+            }
+            Ok(true) => continue,
+            Ok(false) => {}
+        }
+
         //? Process line
         let r = process_line!(
             VariableInitialisationWithArgumentLine,
@@ -221,6 +281,7 @@ pub fn process_symbols(symbols: Vec<(usize, Vec<Symbol>)>) -> Result<MemoryManag
                 block_coordinator
             )
         })
+        .or_else(|| process_line!(ReturnLine, symbol_line, memory_managers, block_coordinator))
         .or_else(|| process_line!(CallLine, symbol_line, memory_managers, block_coordinator))
         .or_else(|| {
             process_line!(
@@ -239,9 +300,13 @@ pub fn process_symbols(symbols: Vec<(usize, Vec<Symbol>)>) -> Result<MemoryManag
             )
         })
         .or_else(|| {
-            process_line!(IfLine, symbol_line, memory_managers, block_coordinator).or_else(|| {
-                process_line!(WhileLine, symbol_line, memory_managers, block_coordinator)
-            })
+            process_line!(IfLine, symbol_line, memory_managers, block_coordinator)
+                .or_else(|| {
+                    process_line!(WhileLine, symbol_line, memory_managers, block_coordinator)
+                })
+                .or_else(|| {
+                    process_line!(LoopLine, symbol_line, memory_managers, block_coordinator)
+                })
         })
         .or_else(|| {
             process_line!(
@@ -251,6 +316,7 @@ pub fn process_symbols(symbols: Vec<(usize, Vec<Symbol>)>) -> Result<MemoryManag
                 block_coordinator
             )
         })
+        .or_else(|| process_line!(StructLine, symbol_line, memory_managers, block_coordinator))
         .or_else(|| process_line!(PrintLine, symbol_line, memory_managers, block_coordinator))
         .or_else(|| process_line!(InputLine, symbol_line, memory_managers, block_coordinator))
         .or_else(|| {
@@ -262,24 +328,41 @@ pub fn process_symbols(symbols: Vec<(usize, Vec<Symbol>)>) -> Result<MemoryManag
             )
         });
 
+        if block_coordinator.get_indentation() > block_starts.len() {
+            block_starts.push((line_index, line_span));
+        }
+
         //? Handle unmatched / failed line
+        //? The body of a block that failed to start can't be compiled, but other lines can
+        if (r.is_failure() || r.is_unmatched()) && opens_block(&symbol_line) {
+            failed_indentation = Some(indentation);
+        }
         if r.is_failure() {
-            return create_line_error(r.get_error(), line_index);
+            errors.push(Diagnostic::error(r.get_error(), line_index, line_span));
         } else if r.is_unmatched() {
-            return create_line_error(
-                "Line didn't match any known patterns".to_string(),
+            errors.push(Diagnostic::error(
+                CompileError::UnknownLine,
                 line_index,
-            );
+                line_span,
+            ));
         }
     }
 
     //? Exit remaining blocks
     while block_coordinator.get_indentation() >= 1 {
+        let (start_index, start_span) = block_starts.pop().unwrap();
         let result = block_coordinator.force_exit_block_handler(&mut memory_managers);
         if let Err(e) = result {
-            return create_line_error(e, line_count);
+            errors.push(Diagnostic::error(e, start_index, start_span));
         }
     }
 
-    Ok(memory_managers)
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    debug_info.variables = block_coordinator
+        .get_reference_stack_mut()
+        .take_variable_history(memory_managers.program_memory.get_position());
+    Ok((memory_managers, debug_info))
 }
@@ -1,24 +1,33 @@
-use crate::processing::types::Type;
+use crate::debug_info::VariableEntry;
+use crate::errors::CompileError;
+use crate::processing::symbols::Symbol;
+use crate::processing::types::structure::StructDefinition;
+use crate::processing::types::{Type, TypeSymbol};
 
 #[derive(Default)]
 pub struct ReferenceStack {
     stack: Vec<ReferenceManager>,
+    /// Every struct declared so far. Indexed by `TypeSymbol::Struct`
+    struct_definitions: Vec<StructDefinition>,
+    /// Every variable registered so far, for debug info
+    variable_history: Vec<VariableEntry>,
+    /// Program address that code is currently being compiled at. Used for variable scopes
+    program_position: usize,
 }
 
 impl ReferenceStack {
     pub fn new() -> Self {
         ReferenceStack {
             stack: vec![ReferenceManager::new()],
+            struct_definitions: Vec::new(),
+            variable_history: Vec::new(),
+            program_position: 0,
         }
     }
 
     /// Registers a variable
-    pub fn register_variable(&mut self, variable: Type, name: String) -> Result<(), String> {
-        return self
-            .stack
-            .last_mut()
-            .unwrap()
-            .register_variable(variable, name);
+    pub fn register_variable(&mut self, variable: Type, name: String) -> Result<(), CompileError> {
+        self.register_variable_with_offset(variable, name, 0)
     }
 
     /// Registers a variable at a layer `offset` above the current one
@@ -27,21 +36,138 @@ impl ReferenceStack {
         variable: Type,
         name: String,
         offset: usize,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
+        let entry = VariableEntry {
+            name: name.clone(),
+            type_name: self.get_type_name(&variable),
+            address: variable.get_address(),
+            size: variable.get_size() * variable.get_len(),
+            scope_start: self.program_position,
+            scope_end: usize::MAX,
+        };
+
         let len = self.stack.len();
-        self.stack[(len - 1) - offset].register_variable(variable, name)
+        let layer = &mut self.stack[(len - 1) - offset];
+        layer.register_variable(variable, name)?;
+        layer.history.push(self.variable_history.len());
+        self.variable_history.push(entry);
+        Ok(())
+    }
+
+    /// Gets the name of a variable's type e.g. `Integer[3]`
+    fn get_type_name(&self, variable: &Type) -> String {
+        let name = match variable.get_type() {
+            TypeSymbol::Struct(id) => self.get_struct_definition(id).get_name().to_string(),
+            type_symbol => type_symbol.to_string(),
+        };
+        if variable.is_indexed() {
+            format!("{}[{}]", name, variable.get_len())
+        } else {
+            name
+        }
+    }
+
+    /// Sets the program address that code is currently being compiled at
+    pub fn set_program_position(&mut self, position: usize) {
+        self.program_position = position;
+    }
+
+    /// Takes every variable registered so far. Variables still in scope are given a scope ending
+    /// at `program_end`
+    pub fn take_variable_history(&mut self, program_end: usize) -> Vec<VariableEntry> {
+        let mut history = std::mem::take(&mut self.variable_history);
+        for entry in history.iter_mut() {
+            entry.scope_end = entry.scope_end.min(program_end);
+        }
+        history
+    }
+
+    /// Marks the current layer as the outermost scope of a function
+    pub fn set_function_scope(&mut self) {
+        self.stack.last_mut().unwrap().function_scope = true;
+    }
+
+    /// Registers a struct at a layer `offset` above the current one
+    pub fn register_struct_with_offset(
+        &mut self,
+        definition: StructDefinition,
+        offset: usize,
+    ) -> Result<(), CompileError> {
+        let len = self.stack.len();
+        let name = definition.get_name().to_string();
+        self.stack[(len - 1) - offset].register_struct(name, self.struct_definitions.len())?;
+        self.struct_definitions.push(definition);
+        Ok(())
+    }
+
+    /// Searches for a struct going up the reference stack
+    pub fn get_struct(&self, name: &str) -> Result<TypeSymbol, CompileError> {
+        for reference_manager in self.stack.iter().rev() {
+            if let Some(id) = reference_manager.get_struct(name) {
+                return Ok(TypeSymbol::Struct(id));
+            }
+        }
+
+        Err(CompileError::UnknownStruct(name.to_string()))
+    }
+
+    /// Gets the definition of a struct
+    pub fn get_struct_definition(&self, id: usize) -> &StructDefinition {
+        &self.struct_definitions[id]
+    }
+
+    /// Gets the `TypeSymbol` a symbol refers to - either a built-in type or a struct name
+    pub fn get_type_symbol(&self, symbol: &Symbol) -> Option<TypeSymbol> {
+        match symbol {
+            Symbol::Type(type_symbol) => Some(*type_symbol),
+            Symbol::Name(name, _) => self.get_struct(name).ok(),
+            _ => None,
+        }
+    }
+
+    /// Searches for a variable going up the reference stack. Struct fields can be accessed with
+    /// '.' e.g. `a.b.c`
+    pub fn get_variable(&self, name: &str) -> Result<&Type, CompileError> {
+        let mut path = name.split('.');
+        let mut variable = self.get_named_variable(path.next().unwrap())?;
+        for field in path {
+            variable = match variable.get_type() {
+                TypeSymbol::Struct(id) => {
+                    variable
+                        .get_field(field)
+                        .map_err(|_| CompileError::UnknownField {
+                            owner: format!(
+                                "Struct '{}'",
+                                self.get_struct_definition(id).get_name()
+                            ),
+                            field: field.to_string(),
+                        })?
+                }
+                _ => variable.get_field(field)?,
+            };
+        }
+        Ok(variable)
     }
 
     /// Searches for a variable going up the reference stack
-    pub fn get_variable(&self, name: &str) -> Result<&Type, String> {
+    fn get_named_variable(&self, name: &str) -> Result<&Type, CompileError> {
         //? Go up the stack and search for a variable
         let mut i = self.stack.len() - 1;
         let mut reference_manager = &self.stack[i];
+        let mut crossed_function = false;
         loop {
             let r = reference_manager.get_variable(name);
-            if let Some(i) = r {
-                return Ok(i);
+            if let Some(variable) = r {
+                //? Variables in another function's stack frame can't be accessed
+                if crossed_function
+                    && variable.is_frame_relative()
+                    && variable.get_type() != TypeSymbol::Function
+                {
+                    return Err(CompileError::InaccessibleVariable(name.to_string()));
+                }
+                return Ok(variable);
             }
+            crossed_function |= reference_manager.function_scope;
             if i == 0 {
                 break;
             }
@@ -49,7 +175,7 @@ impl ReferenceStack {
             reference_manager = &self.stack[i];
         }
 
-        Err(format!("Variable '{}' not found", name))
+        Err(CompileError::UnknownVariable(name.to_string()))
     }
 
     /// Adds a reference handler (adds a variable scope)
@@ -59,7 +185,11 @@ impl ReferenceStack {
 
     /// Removes a reference handler (removes a variable scope)
     pub fn remove_handler(&mut self) {
-        self.stack.pop();
+        if let Some(layer) = self.stack.pop() {
+            for i in layer.history {
+                self.variable_history[i].scope_end = self.program_position;
+            }
+        }
     }
 
     /*    pub fn start_handler_remove(&mut self) { self.stack_removed = Some(self.stack.pop().unwrap()); }
@@ -75,25 +205,52 @@ impl ReferenceStack {
 #[derive(Default)]
 pub struct ReferenceManager {
     variables: Vec<Type>, // Type, Array Index
+    /// Whether this is the outermost scope of a function
+    function_scope: bool,
+    /// Struct names and their indexes in the `ReferenceStack`
+    structs: Vec<(String, usize)>,
+    /// Indexes of this layer's variables in the `ReferenceStack`'s variable history
+    history: Vec<usize>,
 }
 
 impl ReferenceManager {
     pub fn new() -> Self {
         ReferenceManager {
             variables: Vec::new(),
+            function_scope: false,
+            structs: Vec::new(),
+            history: Vec::new(),
         }
     }
 
     /// Registers a variable
-    pub fn register_variable(&mut self, mut variable: Type, name: String) -> Result<(), String> {
+    pub fn register_variable(
+        &mut self,
+        mut variable: Type,
+        name: String,
+    ) -> Result<(), CompileError> {
         if self.get_variable(name.as_str()).is_some() {
-            return Err(format!("Variable with name '{}' already exists", name));
+            return Err(CompileError::DuplicateVariable(name));
         }
         variable.set_name(name);
         self.variables.push(variable);
         Ok(())
     }
 
+    /// Registers a struct
+    pub fn register_struct(&mut self, name: String, id: usize) -> Result<(), CompileError> {
+        if self.get_struct(name.as_str()).is_some() {
+            return Err(CompileError::DuplicateStruct(name));
+        }
+        self.structs.push((name, id));
+        Ok(())
+    }
+
+    /// Returns the `Some(id)` if the struct exists. If not, returns `None`
+    pub fn get_struct(&self, name: &str) -> Option<usize> {
+        self.structs.iter().find(|s| s.0 == name).map(|s| s.1)
+    }
+
     /// Returns the `Some(variable)` if it exists. If not, returns `None`
     pub fn get_variable(&self, name: &str) -> Option<&Type> {
         self.variables.iter().find(|&v| *v.get_name() == *name)
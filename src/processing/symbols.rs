@@ -1,3 +1,4 @@
+use crate::errors::{CompileError, Span};
 mod assigners;
 mod blocks;
 mod builtins;
@@ -16,6 +17,7 @@ pub use literals::STRING_DELIMITERS;
 pub use operators::Operator;
 use operators::OperatorSymbolHandler;
 
+use super::reference_manager::ReferenceStack;
 pub use super::types::TypeSymbol;
 use super::types::TypeSymbolHandler;
 
@@ -31,22 +33,58 @@ pub use punctuation::PunctuationSymbolHandler;
 pub use keywords::Keyword;
 pub use keywords::KeywordSymbolHandler;
 
+/// A symbol in a line of code. Symbols that errors can refer to record the span of the source
+/// they were created from
 #[derive(PartialEq, Clone, strum_macros::Display)]
 pub enum Symbol {
     Assigner(Assigner),
-    Literal(Literal),
+    Literal(Literal, Span),
     Operator(Operator),
-    ArithmeticBlock(Vec<Symbol>),
-    Indexer(Box<Symbol>),
+    ArithmeticBlock(Vec<Symbol>, Span),
+    Indexer(Box<Symbol>, Span),
     List(Vec<Symbol>),
     Type(TypeSymbol),
     Block(Block),
     Builtin(Builtin),
     Punctuation(Punctuation),
-    Name(String),
+    Name(String, Span),
     Keyword(Keyword),
 }
 
+impl Symbol {
+    /// Gets the span of the source the symbol was created from, if it records one
+    pub fn get_span(&self) -> Option<Span> {
+        match self {
+            Symbol::Literal(_, span)
+            | Symbol::ArithmeticBlock(_, span)
+            | Symbol::Indexer(_, span)
+            | Symbol::Name(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+
+    /// Sets the span of symbols that record one
+    pub fn with_span(mut self, span: Span) -> Self {
+        match &mut self {
+            Symbol::Literal(_, symbol_span)
+            | Symbol::ArithmeticBlock(_, symbol_span)
+            | Symbol::Indexer(_, symbol_span)
+            | Symbol::Name(_, symbol_span) => *symbol_span = span,
+            _ => {}
+        }
+        self
+    }
+}
+
+/// Gets the span covering every symbol that records one
+pub fn get_symbols_span(symbols: &[Symbol]) -> Span {
+    symbols
+        .iter()
+        .filter_map(|symbol| symbol.get_span())
+        .reduce(|span, symbol_span| span.join(&symbol_span))
+        .unwrap_or_default()
+}
+
 pub trait SymbolHandler {
     /// Converts a string to a symbol. Returns `None` if no symbol matches the string
     fn get_symbol(string: &str) -> Option<Symbol>;
@@ -58,13 +96,18 @@ pub fn get_all_symbol(string: &str) -> Option<Symbol> {
 }
 
 /// Converts an arithmetic block into a `Literal::ParameterList(parameters)`
-pub fn try_arithmetic_block_into_parameters(arithmetic_block: &Symbol) -> Result<Literal, String> {
-    fn formatting_error() -> String {
-        "Parameters must be formatted ([Type] [Name] , [Type] [Name] , [...])".to_string()
+pub fn try_arithmetic_block_into_parameters(
+    arithmetic_block: &Symbol,
+    reference_stack: &ReferenceStack,
+) -> Result<Literal, CompileError> {
+    fn formatting_error() -> CompileError {
+        CompileError::Syntax(
+            "Parameters must be formatted ([Type] [Name] , [Type] [Name] , [...])".to_string(),
+        )
     }
 
     let list = match arithmetic_block {
-        Symbol::ArithmeticBlock(list) => list,
+        Symbol::ArithmeticBlock(list, _) => list,
         _ => panic!("Must be arithmetic block"),
     };
 
@@ -72,7 +115,7 @@ pub fn try_arithmetic_block_into_parameters(arithmetic_block: &Symbol) -> Result
         return Ok(Literal::ParameterList(Vec::new()));
     }
 
-    let mut parameter_list: Vec<(TypeSymbol, String)> = Vec::new();
+    let mut parameter_list: Vec<(TypeSymbol, String, Span)> = Vec::new();
 
     let mut i: usize = 0;
 
@@ -83,14 +126,14 @@ pub fn try_arithmetic_block_into_parameters(arithmetic_block: &Symbol) -> Result
         }
 
         // No type
-        let type_symbol = match list[i] {
-            Symbol::Type(type_symbol) => type_symbol,
-            _ => return Err(formatting_error()),
+        let type_symbol = match reference_stack.get_type_symbol(&list[i]) {
+            Some(type_symbol) => type_symbol,
+            None => return Err(formatting_error()),
         };
 
         // No name
-        let name = match &list[i + 1] {
-            Symbol::Name(name) => name.clone(),
+        let (name, span) = match &list[i + 1] {
+            Symbol::Name(name, span) => (name.clone(), *span),
             _ => return Err(formatting_error()),
         };
 
@@ -109,7 +152,7 @@ pub fn try_arithmetic_block_into_parameters(arithmetic_block: &Symbol) -> Result
             }
         }
 
-        parameter_list.push((type_symbol, name));
+        parameter_list.push((type_symbol, name, span));
 
         i += 3;
     }
@@ -132,13 +175,19 @@ impl SymbolHandler for AllSymbolHandler {
             .or_else(|| PunctuationSymbolHandler::get_symbol(string))
             .or_else(|| KeywordSymbolHandler::get_symbol(string))
             .or_else(|| {
-                for c in string.chars() {
-                    if !ALLOWED_CHARS_IN_NAME.contains(c) {
+                //? Names can be joined with '.' to access struct fields e.g. 'a.b'
+                for part in string.split('.') {
+                    if part.is_empty() {
                         return None;
                     }
+                    for c in part.chars() {
+                        if !ALLOWED_CHARS_IN_NAME.contains(c) {
+                            return None;
+                        }
+                    }
                 }
 
-                Some(Symbol::Name(String::from(string)))
+                Some(Symbol::Name(String::from(string), Span::default()))
             })
     }
 }
@@ -1,6 +1,7 @@
 use super::Operator;
 use super::Symbol;
 use super::SymbolHandler;
+use crate::processing::symbols::get_symbols_span;
 use crate::processing::symbols::Symbol::ArithmeticBlock;
 
 #[derive(PartialEq, Copy, Clone, strum_macros::Display)]
@@ -10,21 +11,28 @@ pub enum Assigner {
     SubtractionSetter,
     ProductSetter,
     DivisionSetter,
+    ModuloSetter,
 }
 
 impl Assigner {
     pub fn get_expanded_equivalent(&self, lhs: Symbol, rhs: Vec<Symbol>) -> Vec<Symbol> {
+        let rhs_span = get_symbols_span(&rhs);
         let equivalent = match self {
             Assigner::Setter => {
-                return vec![ArithmeticBlock(rhs)];
+                return vec![ArithmeticBlock(rhs, rhs_span)];
             }
             Assigner::AdditionSetter => Operator::Add,
             Assigner::SubtractionSetter => Operator::Subtract,
             Assigner::ProductSetter => Operator::Product,
             Assigner::DivisionSetter => Operator::Divide,
+            Assigner::ModuloSetter => Operator::Modulo,
         };
 
-        vec![lhs, Symbol::Operator(equivalent), ArithmeticBlock(rhs)]
+        vec![
+            lhs,
+            Symbol::Operator(equivalent),
+            ArithmeticBlock(rhs, rhs_span),
+        ]
     }
 }
 
@@ -38,6 +46,7 @@ impl SymbolHandler for AssignerSymbolHandler {
             "-=" => Some(Symbol::Assigner(Assigner::SubtractionSetter)),
             "*=" => Some(Symbol::Assigner(Assigner::ProductSetter)),
             "/=" => Some(Symbol::Assigner(Assigner::DivisionSetter)),
+            "%=" => Some(Symbol::Assigner(Assigner::ModuloSetter)),
             _ => None,
         }
     }
@@ -1,5 +1,6 @@
 use super::Symbol;
 use super::SymbolHandler;
+use crate::errors::Span;
 use crate::processing::types::TypeSymbol;
 
 #[derive(PartialEq, Clone, strum_macros::Display)]
@@ -7,7 +8,8 @@ pub enum Literal {
     String(String),
     Int(i64),
     Bool(bool),
-    ParameterList(Vec<(TypeSymbol, String)>),
+    /// Type, name and span of each parameter
+    ParameterList(Vec<(TypeSymbol, String, Span)>),
     None,
 }
 
@@ -45,9 +47,9 @@ impl SymbolHandler for LiteralSymbolHandler {
     fn get_symbol(string: &str) -> Option<Symbol> {
         (match string {
             // Boolean
-            "true" => Some(Symbol::Literal(Literal::Bool(true))),
-            "false" => Some(Symbol::Literal(Literal::Bool(false))),
-            "none" => Some(Symbol::Literal(Literal::None)),
+            "true" => Some(Symbol::Literal(Literal::Bool(true), Span::default())),
+            "false" => Some(Symbol::Literal(Literal::Bool(false), Span::default())),
+            "none" => Some(Symbol::Literal(Literal::None, Span::default())),
             _ => None,
         })
         .or_else(
@@ -57,9 +59,12 @@ impl SymbolHandler for LiteralSymbolHandler {
                     && STRING_DELIMITERS.contains(&string.chars().next().unwrap())
                     && string.chars().last().unwrap() == string.chars().next().unwrap()
                 {
-                    return Some(Symbol::Literal(Literal::String(format_escape_codes(
-                        string[1..string.len() - 1].to_string(),
-                    ))));
+                    return Some(Symbol::Literal(
+                        Literal::String(format_escape_codes(
+                            string[1..string.len() - 1].to_string(),
+                        )),
+                        Span::default(),
+                    ));
                 }
                 None
             },
@@ -67,7 +72,7 @@ impl SymbolHandler for LiteralSymbolHandler {
         .or_else(
             // Integer
             || match string.parse::<i64>() {
-                Ok(ok) => Some(Symbol::Literal(Literal::Int(ok))),
+                Ok(ok) => Some(Symbol::Literal(Literal::Int(ok), Span::default())),
                 Err(_) => None,
             },
         )
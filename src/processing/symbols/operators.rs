@@ -1,12 +1,13 @@
 use super::Symbol;
 use super::SymbolHandler;
 
-#[derive(PartialEq, Copy, Clone, strum_macros::Display)]
+#[derive(PartialEq, Copy, Clone, strum_macros::Display, Debug)]
 pub enum Operator {
     Add,
     Subtract,
     Product,
     Divide,
+    Modulo,
     Greater,
     Less,
     GreaterEqual,
@@ -27,6 +28,7 @@ impl SymbolHandler for OperatorSymbolHandler {
             "-" => Some(Symbol::Operator(Operator::Subtract)),
             "*" => Some(Symbol::Operator(Operator::Product)),
             "/" => Some(Symbol::Operator(Operator::Divide)),
+            "%" => Some(Symbol::Operator(Operator::Modulo)),
             ">" => Some(Symbol::Operator(Operator::Greater)),
             "<" => Some(Symbol::Operator(Operator::Less)),
             ">=" => Some(Symbol::Operator(Operator::GreaterEqual)),
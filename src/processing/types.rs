@@ -1,17 +1,27 @@
 pub mod boolean;
 pub mod char;
 pub mod function;
+pub mod integer;
 pub mod pointer;
+pub mod string;
+pub mod structure;
 
-use crate::errors::create_op_not_impl_error;
+use crate::errors::{create_op_not_impl_error, CompileError};
+use crate::memory_manager::FRAME_RELATIVE_FLAG;
 use crate::processing::instructions::copy_instruction_0::CopyInstruction;
 use crate::processing::instructions::dynamic_from_copy_instruction_10::DynamicFromCopyInstruction;
 use crate::processing::instructions::dynamic_to_copy_instruction_11::DynamicToCopyInstruction;
+use crate::processing::instructions::greater_equal_instruction_21::GreaterEqualInstruction;
+use crate::processing::instructions::greater_instruction_20::GreaterInstruction;
 use crate::processing::processor::MemoryManagers;
+use crate::processing::reference_manager::ReferenceStack;
 use crate::processing::symbols::{Literal, Operator, Symbol, SymbolHandler};
 use crate::processing::types::boolean::BooleanType;
 use crate::processing::types::char::CharType;
+use crate::processing::types::integer::IntegerType;
 use crate::processing::types::pointer::PointerType;
+use crate::processing::types::string::StringType;
+use crate::processing::types::structure::StructType;
 
 macro_rules! create_type {
     ($internal_type: ident, $memory_managers: expr) => {
@@ -23,28 +33,166 @@ macro_rules! create_type {
 pub fn get_type(
     type_symbol: &TypeSymbol,
     memory_managers: &mut MemoryManagers,
-) -> Result<Type, String> {
+) -> Result<Type, CompileError> {
     match type_symbol {
         TypeSymbol::Boolean => Ok(create_type!(BooleanType, memory_managers)),
         TypeSymbol::Character => Ok(create_type!(CharType, memory_managers)),
+        TypeSymbol::Integer => Ok(create_type!(IntegerType, memory_managers)),
         TypeSymbol::Pointer => Ok(create_type!(PointerType, memory_managers)),
-        type_symbol => Err(format!(
+        TypeSymbol::String => Ok(create_type!(StringType, memory_managers)),
+        type_symbol => Err(CompileError::UnsupportedOperation(format!(
             "{:?}(s) cannot be created! (Are you trying to operate on an invalid type?)",
             type_symbol
-        )),
+        ))),
     }
 }
 
+/// Converts a `TypeSymbol` to an instantiated `Type`, including structs declared in
+/// `reference_stack`
+pub fn get_scoped_type(
+    type_symbol: &TypeSymbol,
+    memory_managers: &mut MemoryManagers,
+    reference_stack: &ReferenceStack,
+) -> Result<Type, CompileError> {
+    match type_symbol {
+        TypeSymbol::Struct(id) => {
+            let address = memory_managers
+                .variable_memory
+                .allocate(reference_stack.get_struct_definition(*id).get_size());
+            StructType::create_at(*id, address, reference_stack)
+        }
+        type_symbol => get_type(type_symbol, memory_managers),
+    }
+}
+
+/// Converts a `TypeSymbol` to an instantiated `Type` in static memory, even when inside a
+/// function's stack frame
+pub fn get_static_type(
+    type_symbol: &TypeSymbol,
+    memory_managers: &mut MemoryManagers,
+    reference_stack: &ReferenceStack,
+) -> Result<Type, CompileError> {
+    let frames = memory_managers.variable_memory.suspend_frames();
+    let result = get_scoped_type(type_symbol, memory_managers, reference_stack);
+    memory_managers.variable_memory.resume_frames(frames);
+    result
+}
+
+/// Creates a `Type` at `address` without allocating memory for it
+pub(crate) fn create_type_at(
+    type_symbol: &TypeSymbol,
+    address: usize,
+    reference_stack: &ReferenceStack,
+) -> Result<Type, CompileError> {
+    let internal_type: Box<dyn TypeTrait> = match type_symbol {
+        TypeSymbol::Struct(id) => return StructType::create_at(*id, address, reference_stack),
+        TypeSymbol::Boolean => Box::new(BooleanType::create_empty()),
+        TypeSymbol::Character => Box::new(CharType::create_empty()),
+        TypeSymbol::Integer => Box::new(IntegerType::create_empty()),
+        TypeSymbol::Pointer => Box::new(PointerType::create_empty()),
+        TypeSymbol::String => Box::new(StringType::create_empty()),
+        type_symbol => {
+            return Err(CompileError::UnsupportedOperation(format!(
+                "{:?}(s) cannot be created! (Are you trying to operate on an invalid type?)",
+                type_symbol
+            )))
+        }
+    };
+
+    Ok(Type {
+        internal_type,
+        name: None,
+        address,
+        indexed_len: None,
+    })
+}
+
+/// Gets the size of a `TypeSymbol` without creating it
+pub fn get_type_size(
+    type_symbol: &TypeSymbol,
+    reference_stack: &ReferenceStack,
+) -> Result<usize, CompileError> {
+    Ok(create_type_at(type_symbol, 0, reference_stack)?.get_size())
+}
+
 /// Converts a `Literal` to the default `Type` for that type of `Literal`
 pub fn get_type_from_literal(
     literal: &Literal,
     memory_managers: &mut MemoryManagers,
-) -> Result<Type, String> {
+) -> Result<Type, CompileError> {
     match literal {
         Literal::Bool(_) => Ok(create_type!(BooleanType, memory_managers)),
-        Literal::String(_) => Ok(create_type!(CharType, memory_managers)),
+        Literal::String(string) if string.chars().count() == 1 => {
+            Ok(create_type!(CharType, memory_managers))
+        }
+        Literal::String(_) => Ok(create_type!(StringType, memory_managers)),
+        Literal::Int(integer) if *integer < 0 => Ok(create_type!(IntegerType, memory_managers)),
         Literal::Int(_) => Ok(create_type!(PointerType, memory_managers)),
-        _ => Err(format!("Cannot infer type from {}", literal)),
+        _ => Err(CompileError::InvalidLiteral(format!(
+            "Cannot infer type from {}",
+            literal
+        ))),
+    }
+}
+
+/// Converts a `Literal` to a `Type` matching `type_hint` (e.g. the other operand's type) if
+/// the `Literal` can represent it. Falls back to `get_type_from_literal` otherwise
+pub fn get_type_from_literal_with_hint(
+    literal: &Literal,
+    type_hint: Option<TypeSymbol>,
+    memory_managers: &mut MemoryManagers,
+) -> Result<Type, CompileError> {
+    match (type_hint, literal) {
+        (Some(TypeSymbol::Integer), Literal::Int(_)) => {
+            Ok(create_type!(IntegerType, memory_managers))
+        }
+        (Some(TypeSymbol::Pointer), Literal::Int(integer)) if *integer >= 0 => {
+            Ok(create_type!(PointerType, memory_managers))
+        }
+        (Some(TypeSymbol::String), Literal::String(_)) => {
+            Ok(create_type!(StringType, memory_managers))
+        }
+        _ => get_type_from_literal(literal, memory_managers),
+    }
+}
+
+/// Creates the instruction for an ordering comparison (`>`, `<`, `>=` or `<=`) between two
+/// `Type`s of the same size. `<` and `<=` are created by swapping the operands of `>` and `>=`
+pub(crate) fn create_ordering_comparison(
+    memory_managers: &mut MemoryManagers,
+    operator: &Operator,
+    lhs: &Type,
+    rhs: &Type,
+    destination: &Type,
+    signed: bool,
+) {
+    let (lhs, rhs) = match operator {
+        Operator::Greater | Operator::GreaterEqual => (lhs, rhs),
+        Operator::Less | Operator::LessEqual => (rhs, lhs),
+        _ => panic!("{} is not an ordering comparison", operator),
+    };
+
+    match operator {
+        Operator::Greater | Operator::Less => {
+            GreaterInstruction::new_alloc(
+                memory_managers,
+                lhs.get_address(),
+                rhs.get_address(),
+                lhs.get_size(),
+                destination.get_address(),
+                signed,
+            );
+        }
+        _ => {
+            GreaterEqualInstruction::new_alloc(
+                memory_managers,
+                lhs.get_address(),
+                rhs.get_address(),
+                lhs.get_size(),
+                destination.get_address(),
+                signed,
+            );
+        }
     }
 }
 
@@ -55,6 +203,9 @@ pub enum TypeSymbol {
     Character,
     Function,
     Pointer,
+    String,
+    /// A user-defined struct, identified by its index in the `ReferenceStack`
+    Struct(usize),
 }
 
 pub struct TypeSymbolHandler {}
@@ -66,6 +217,7 @@ impl SymbolHandler for TypeSymbolHandler {
             "bool" => Some(Symbol::Type(TypeSymbol::Boolean)),
             "char" => Some(Symbol::Type(TypeSymbol::Character)),
             "ptr" => Some(Symbol::Type(TypeSymbol::Pointer)),
+            "str" => Some(Symbol::Type(TypeSymbol::String)),
             _ => None,
         }
     }
@@ -82,7 +234,7 @@ impl Type {
     pub fn new(internal_type: Box<dyn TypeTrait>, memory_managers: &mut MemoryManagers) -> Self {
         let address = memory_managers
             .variable_memory
-            .reserve(internal_type.get_size());
+            .allocate(internal_type.get_size());
 
         Self {
             internal_type,
@@ -117,9 +269,11 @@ impl Type {
         &self,
         memory_managers: &mut MemoryManagers,
         to_clone: &Type,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         if self.is_indexed() {
-            return Err("Tried to assign to type that needs indexing".to_string());
+            return Err(CompileError::UnsupportedOperation(
+                "Tried to assign to type that needs indexing".to_string(),
+            ));
         }
         self.internal_type
             .assign_clone(self, memory_managers, to_clone)
@@ -130,9 +284,11 @@ impl Type {
         &self,
         memory_managers: &mut MemoryManagers,
         literal: &Literal,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         if self.is_indexed() {
-            return Err("Tried to assign to type that needs indexing".to_string());
+            return Err(CompileError::UnsupportedOperation(
+                "Tried to assign to type that needs indexing".to_string(),
+            ));
         }
         self.internal_type
             .static_assign_literal(self, memory_managers, literal)
@@ -144,16 +300,15 @@ impl Type {
         _memory_managers: &mut MemoryManagers,
         _argument_literal: &Literal,
         _assignment_literal: &Literal,
-    ) -> Result<usize, String> {
+    ) -> Result<usize, CompileError> {
         let result = self.internal_type.create_indexed(
             self,
             _memory_managers,
             _argument_literal,
             _assignment_literal,
-        );
-        result.as_ref()?;
-        self.indexed_len = Some(*result.as_ref().unwrap());
-        result
+        )?;
+        self.indexed_len = Some(result);
+        Ok(result)
     }
 
     /// Gets the value at an index and assigns it to the `destination`
@@ -162,24 +317,56 @@ impl Type {
         memory_managers: &mut MemoryManagers,
         index_pointer: &Type,
         destination: &Type,
-    ) -> Result<(), String> {
-        if !self.is_indexed() {
-            return Err("Tried to index type that isn't indexed".to_string());
+    ) -> Result<(), CompileError> {
+        if !self.is_indexed() && !self.internal_type.is_indexable() {
+            return Err(CompileError::UnsupportedOperation(
+                "Tried to index type that isn't indexed".to_string(),
+            ));
         }
 
         self.internal_type
             .get_index(self, memory_managers, index_pointer, destination)
     }
 
+    /// Gets the values between the `start` (inclusive) and `end` (exclusive) indexes and assigns
+    /// them to the `destination`
+    pub fn get_slice(
+        &self,
+        memory_managers: &mut MemoryManagers,
+        start: &Type,
+        end: &Type,
+        destination: &Type,
+    ) -> Result<(), CompileError> {
+        self.internal_type
+            .get_slice(self, memory_managers, start, end, destination)
+    }
+
+    /// Gets the `TypeSymbol` of the values produced by indexing this `Type`
+    pub fn get_element_type(&self) -> TypeSymbol {
+        self.internal_type.get_element_type()
+    }
+
+    /// Gets a field of this `Type` if it is a struct
+    pub fn get_field(&self, name: &str) -> Result<&Type, CompileError> {
+        self.internal_type.get_field(name)
+    }
+
+    /// Gets all fields of this `Type` in memory order. Empty if this `Type` has no fields
+    pub fn get_fields(&self) -> Vec<&Type> {
+        self.internal_type.get_fields()
+    }
+
     /// Sets the value at an index to `source`
     pub fn set_indexed(
         &self,
         memory_managers: &mut MemoryManagers,
         index_pointer: &Type,
         source: &Type,
-    ) -> Result<(), String> {
-        if !self.is_indexed() {
-            return Err("Tried to index type that isn't indexed".to_string());
+    ) -> Result<(), CompileError> {
+        if !self.is_indexed() && !self.internal_type.is_indexable() {
+            return Err(CompileError::UnsupportedOperation(
+                "Tried to index type that isn't indexed".to_string(),
+            ));
         }
 
         self.internal_type
@@ -192,15 +379,25 @@ impl Type {
     }
 
     /// Gets return type if this `Type` can be called
-    pub fn get_return_type(&self) -> Result<TypeSymbol, String> {
+    pub fn get_return_type(&self) -> Result<TypeSymbol, CompileError> {
         self.internal_type.get_return_type()
     }
 
+    /// Gets parameter types if this `Type` can be called
+    pub fn get_parameter_types(&self) -> Result<Vec<TypeSymbol>, CompileError> {
+        self.internal_type.get_parameter_types()
+    }
+
     /// Gets the variable memory address of this `Type`
     pub fn get_address(&self) -> usize {
         self.address
     }
 
+    /// Gets whether this `Type` is stored in a function's stack frame
+    pub fn is_frame_relative(&self) -> bool {
+        self.address & FRAME_RELATIVE_FLAG != 0
+    }
+
     /// Gets the size of this `Type`
     pub fn get_size(&self) -> usize {
         self.internal_type.get_size()
@@ -212,7 +409,7 @@ impl Type {
         memory_managers: &mut MemoryManagers,
         arguments: Vec<&Type>,
         destination: Option<&Type>,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         self.internal_type
             .call(memory_managers, arguments, destination)
     }
@@ -222,7 +419,7 @@ impl Type {
         &self,
         operator: &Operator,
         rhs: Option<&Type>,
-    ) -> Result<TypeSymbol, String> {
+    ) -> Result<TypeSymbol, CompileError> {
         self.internal_type.get_operation_type(self, operator, rhs)
     }
 
@@ -233,7 +430,7 @@ impl Type {
         operator: Operator,
         rhs: Option<&Type>,
         destination: &Type,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         self.internal_type
             .operate(self, memory_managers, operator, rhs, destination)
     }
@@ -256,13 +453,12 @@ pub trait TypeTrait {
         _super: &Type,
         memory_managers: &mut MemoryManagers,
         to_clone: &Type,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         if self.get_type() != to_clone.get_type() {
-            return Err(format!(
-                "Mismatching types for assignment: {} -> {}",
-                to_clone.get_type(),
-                self.get_type()
-            ));
+            return Err(CompileError::TypeMismatch {
+                expected: self.get_type(),
+                found: to_clone.get_type(),
+            });
         }
 
         CopyInstruction::new_alloc(
@@ -281,11 +477,11 @@ pub trait TypeTrait {
         _super: &Type,
         _memory_managers: &mut MemoryManagers,
         _literal: &Literal,
-    ) -> Result<(), String> {
-        Err(format!(
+    ) -> Result<(), CompileError> {
+        Err(CompileError::UnsupportedOperation(format!(
             "Assignment from literals not implemented for {}",
             self.get_type()
-        ))
+        )))
     }
 
     /// Creates an indexed `Type`
@@ -295,11 +491,11 @@ pub trait TypeTrait {
         _memory_managers: &mut MemoryManagers,
         _argument_literal: &Literal,
         _assignment_literal: &Literal,
-    ) -> Result<usize, String> {
-        Err(format!(
+    ) -> Result<usize, CompileError> {
+        Err(CompileError::UnsupportedOperation(format!(
             "{} cannot be created with initialisation argument",
             self.get_type()
-        ))
+        )))
     }
 
     /// Gets the value at an index and assigns it to the `destination`
@@ -309,18 +505,22 @@ pub trait TypeTrait {
         memory_managers: &mut MemoryManagers,
         index_pointer: &Type,
         destination: &Type,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         match index_pointer.get_type() {
             TypeSymbol::Pointer => {}
-            _ => return Err("Only pointers are supported for indexing this type".to_string()),
+            found => {
+                return Err(CompileError::TypeMismatch {
+                    expected: TypeSymbol::Pointer,
+                    found,
+                })
+            }
         }
 
         if self.get_type() != destination.get_type() {
-            return Err(format!(
-                "Cannot move value from indexed {} into {}",
-                self.get_type(),
-                destination.get_type()
-            ));
+            return Err(CompileError::TypeMismatch {
+                expected: destination.get_type(),
+                found: self.get_type(),
+            });
         }
 
         // info(format!("{}", index_pointer.get_address()).as_str());
@@ -332,6 +532,7 @@ pub trait TypeTrait {
             index_pointer.get_address(),
             destination.address,
             self.get_size(),
+            _super.get_len(),
         );
 
         Ok(())
@@ -344,18 +545,22 @@ pub trait TypeTrait {
         memory_managers: &mut MemoryManagers,
         index_pointer: &Type,
         source: &Type,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         match index_pointer.get_type() {
             TypeSymbol::Pointer => {}
-            _ => return Err("Only pointers are supported for indexing this type".to_string()),
+            found => {
+                return Err(CompileError::TypeMismatch {
+                    expected: TypeSymbol::Pointer,
+                    found,
+                })
+            }
         }
 
         if self.get_type() != source.get_type() {
-            return Err(format!(
-                "Cannot move value from {} into indexed {}",
-                source.get_type(),
-                self.get_type()
-            ));
+            return Err(CompileError::TypeMismatch {
+                expected: self.get_type(),
+                found: source.get_type(),
+            });
         }
 
         DynamicToCopyInstruction::new_alloc(
@@ -365,17 +570,69 @@ pub trait TypeTrait {
             index_pointer.get_address(),
             source.address,
             self.get_size(),
+            _super.get_len(),
         );
 
         Ok(())
     }
 
+    /// Gets whether a single value of this `Type` can be indexed e.g. strings. Indexed (array)
+    /// `Type`s can always be indexed
+    fn is_indexable(&self) -> bool {
+        false
+    }
+
+    /// Gets the `TypeSymbol` of the values produced by indexing this `Type`
+    fn get_element_type(&self) -> TypeSymbol {
+        self.get_type()
+    }
+
+    /// Gets the values between the `start` (inclusive) and `end` (exclusive) indexes and assigns
+    /// them to the `destination`
+    fn get_slice(
+        &self,
+        _super: &Type,
+        _memory_managers: &mut MemoryManagers,
+        _start: &Type,
+        _end: &Type,
+        _destination: &Type,
+    ) -> Result<(), CompileError> {
+        Err(CompileError::UnsupportedOperation(format!(
+            "{} cannot be sliced",
+            self.get_type()
+        )))
+    }
+
+    /// Gets a field of this `Type` if it is a struct
+    fn get_field(&self, name: &str) -> Result<&Type, CompileError> {
+        Err(CompileError::UnknownField {
+            owner: self.get_type().to_string(),
+            field: name.to_string(),
+        })
+    }
+
+    /// Gets all fields of this `Type` in memory order. Empty if this `Type` has no fields
+    fn get_fields(&self) -> Vec<&Type> {
+        Vec::new()
+    }
+
     /// Gets the `TypeSymbol` corresponding to this `Type`
     fn get_type(&self) -> TypeSymbol;
 
     /// Gets return type if this `Type` can be called
-    fn get_return_type(&self) -> Result<TypeSymbol, String> {
-        Err(format!("{} cannot be called", self.get_type()))
+    fn get_return_type(&self) -> Result<TypeSymbol, CompileError> {
+        Err(CompileError::UnsupportedOperation(format!(
+            "{} cannot be called",
+            self.get_type()
+        )))
+    }
+
+    /// Gets parameter types if this `Type` can be called
+    fn get_parameter_types(&self) -> Result<Vec<TypeSymbol>, CompileError> {
+        Err(CompileError::UnsupportedOperation(format!(
+            "{} cannot be called",
+            self.get_type()
+        )))
     }
 
     /// Gets the size of this `Type`
@@ -387,8 +644,11 @@ pub trait TypeTrait {
         _memory_managers: &mut MemoryManagers,
         _arguments: Vec<&Type>,
         _destination: Option<&Type>,
-    ) -> Result<(), String> {
-        Err(format!("{} cannot be called", self.get_type()))
+    ) -> Result<(), CompileError> {
+        Err(CompileError::UnsupportedOperation(format!(
+            "{} cannot be called",
+            self.get_type()
+        )))
     }
 
     /// Gets the `TypeSymbol` that the given operation would return
@@ -397,7 +657,7 @@ pub trait TypeTrait {
         _lhs: &Type,
         operator: &Operator,
         rhs: Option<&Type>,
-    ) -> Result<TypeSymbol, String> {
+    ) -> Result<TypeSymbol, CompileError> {
         create_op_not_impl_error(operator, self.get_type(), rhs)
     }
 
@@ -409,7 +669,7 @@ pub trait TypeTrait {
         operator: Operator,
         rhs: Option<&Type>,
         _destination: &Type,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         create_op_not_impl_error(&operator, self.get_type(), rhs)
     }
 
@@ -1,4 +1,4 @@
-use crate::errors::create_op_not_impl_error;
+use crate::errors::{create_op_not_impl_error, CompileError};
 use crate::processing::instructions::and_instruction_6::AndInstruction;
 use crate::processing::instructions::copy_instruction_0::CopyInstruction;
 use crate::processing::instructions::equal_instruction_7::EqualInstruction;
@@ -26,7 +26,7 @@ impl TypeTrait for BooleanType {
         _super: &Type,
         memory_managers: &mut MemoryManagers,
         literal: &Literal,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         // Get literal value
         let value: bool;
         match literal {
@@ -37,19 +37,19 @@ impl TypeTrait for BooleanType {
                 } else if *integer == 1 {
                     value = true;
                 } else {
-                    return Err(format!(
+                    return Err(CompileError::InvalidLiteral(format!(
                         "{} can only be assigned {} '0' or '1'",
                         self.get_type(),
                         Literal::Int(0)
-                    ));
+                    )));
                 }
             }
             unhandled_literal => {
-                return Err(format!(
+                return Err(CompileError::InvalidLiteral(format!(
                     "{} not supported for {} assignment",
                     unhandled_literal,
                     self.get_type()
-                ))
+                )))
             }
         }
 
@@ -85,7 +85,7 @@ impl TypeTrait for BooleanType {
         _lhs: &Type,
         operator: &Operator,
         rhs: Option<&Type>,
-    ) -> Result<TypeSymbol, String> {
+    ) -> Result<TypeSymbol, CompileError> {
         if rhs.is_none() {
             return match operator {
                 Operator::Not => Ok(self.get_type()),
@@ -113,7 +113,7 @@ impl TypeTrait for BooleanType {
         operator: Operator,
         rhs: Option<&Type>,
         destination: &Type,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         if rhs.is_none() {
             return match operator {
                 Operator::Not => {
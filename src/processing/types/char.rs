@@ -1,10 +1,10 @@
-use crate::errors::create_op_not_impl_error;
+use crate::errors::{create_op_not_impl_error, CompileError};
 use crate::processing::instructions::copy_instruction_0::CopyInstruction;
 use crate::processing::instructions::equal_instruction_7::EqualInstruction;
 use crate::processing::instructions::not_equal_instruction_14::NotEqualInstruction;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::symbols::{Literal, Operator, TypeSymbol};
-use crate::processing::types::{get_type, Type, TypeTrait};
+use crate::processing::types::{create_ordering_comparison, get_type, Type, TypeTrait};
 
 pub struct CharType {}
 
@@ -20,32 +20,34 @@ impl TypeTrait for CharType {
         _super: &Type,
         memory_managers: &mut MemoryManagers,
         literal: &Literal,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         // Get literal value
         let value: u8;
         match literal {
             Literal::String(string) => {
                 if string.len() != 1 {
-                    return Err(
-                        "Chars can only be assigned from StringLiterals of length 1".to_string()
-                    );
+                    return Err(CompileError::InvalidLiteral(
+                        "Chars can only be assigned from StringLiterals of length 1".to_string(),
+                    ));
                 }
 
                 value = string.chars().next().unwrap() as u8;
             }
             Literal::Int(integer) => {
                 if *integer < 0 || *integer > 255 {
-                    return Err("Char can be assigned from IntLiterals 0-255 only".to_string());
+                    return Err(CompileError::InvalidLiteral(
+                        "Char can be assigned from IntLiterals 0-255 only".to_string(),
+                    ));
                 }
 
                 value = *integer as u8;
             }
             unhandled_literal => {
-                return Err(format!(
+                return Err(CompileError::InvalidLiteral(format!(
                     "{} not supported for {} assignment",
                     unhandled_literal,
                     self.get_type()
-                ))
+                )))
             }
         }
 
@@ -68,32 +70,39 @@ impl TypeTrait for CharType {
         memory_managers: &mut MemoryManagers,
         argument_literal: &Literal,
         assignment_literal: &Literal,
-    ) -> Result<usize, String> {
+    ) -> Result<usize, CompileError> {
         let count: usize = match argument_literal {
             Literal::Int(count) => match (*count).try_into() {
                 Ok(value) => value,
-                Err(_) => return Err(format!("Initialisation argument '{}' out of range", count)),
+                Err(_) => {
+                    return Err(CompileError::InvalidLiteral(format!(
+                        "Initialisation argument '{}' out of range",
+                        count
+                    )))
+                }
             },
             _ => {
-                return Err(format!(
+                return Err(CompileError::InvalidLiteral(format!(
                     "This type cannot be created with {} initialisation argument",
                     argument_literal
-                ))
+                )))
             }
         };
 
         if count == 0 {
-            return Err("Initialisation argument cannot be 0".to_string());
+            return Err(CompileError::InvalidLiteral(
+                "Initialisation argument cannot be 0".to_string(),
+            ));
         }
 
         let mut assigner = match assignment_literal {
             Literal::String(string) => string.clone(),
             Literal::None => String::new(),
             _ => {
-                return Err(format!(
+                return Err(CompileError::InvalidLiteral(format!(
                     "This type cannot be created with {} assignment argument",
                     assignment_literal
-                ))
+                )))
             }
         };
 
@@ -138,7 +147,7 @@ impl TypeTrait for CharType {
         _lhs: &Type,
         operator: &Operator,
         rhs: Option<&Type>,
-    ) -> Result<TypeSymbol, String> {
+    ) -> Result<TypeSymbol, CompileError> {
         if rhs.is_none() {
             return create_op_not_impl_error(operator, self.get_type(), rhs);
         }
@@ -149,7 +158,12 @@ impl TypeTrait for CharType {
         };
 
         match operator {
-            Operator::Equal | Operator::NotEqual => Ok(TypeSymbol::Boolean),
+            Operator::Equal
+            | Operator::NotEqual
+            | Operator::Greater
+            | Operator::Less
+            | Operator::GreaterEqual
+            | Operator::LessEqual => Ok(TypeSymbol::Boolean),
             _ => create_op_not_impl_error(operator, self.get_type(), rhs),
         }
     }
@@ -161,7 +175,7 @@ impl TypeTrait for CharType {
         operator: Operator,
         rhs: Option<&Type>,
         destination: &Type,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         if rhs.is_none() {
             return create_op_not_impl_error(&operator, self.get_type(), rhs);
         }
@@ -192,6 +206,17 @@ impl TypeTrait for CharType {
                 );
                 Ok(())
             }
+            Operator::Greater | Operator::Less | Operator::GreaterEqual | Operator::LessEqual => {
+                create_ordering_comparison(
+                    memory_managers,
+                    &operator,
+                    lhs,
+                    rhs.unwrap(),
+                    destination,
+                    false,
+                );
+                Ok(())
+            }
             _ => create_op_not_impl_error(&operator, self.get_type(), rhs),
         }
     }
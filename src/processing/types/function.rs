@@ -1,15 +1,15 @@
-use crate::processing::instructions::copy_instruction_0::CopyInstruction;
-use crate::processing::instructions::jump_instruction_3::JumpInstruction;
+use crate::errors::CompileError;
+use crate::processing::instructions::call_instruction_22::CallInstruction;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::symbols::{Literal, TypeSymbol};
 use crate::processing::types::{Type, TypeTrait};
-use std::mem::size_of;
 
+/// A function. `parameters` and `return_type` are static slots used to pass values into and out
+/// of the function's stack frame
 pub struct FunctionType {
     parameters: Vec<Type>,
     return_type: Option<Type>,
     start_address: usize,
-    jump_variable_address: usize,
 }
 
 impl FunctionType {
@@ -17,13 +17,11 @@ impl FunctionType {
         parameters: Vec<Type>,
         return_type: Option<Type>,
         start_address: usize,
-        jump_variable_address: usize,
     ) -> Self {
         Self {
             parameters,
             return_type,
             start_address,
-            jump_variable_address,
         }
     }
 }
@@ -34,11 +32,11 @@ impl TypeTrait for FunctionType {
         _super: &Type,
         _memory_managers: &mut MemoryManagers,
         _to_clone: &Type,
-    ) -> Result<(), String> {
-        Err(format!(
+    ) -> Result<(), CompileError> {
+        Err(CompileError::UnsupportedOperation(format!(
             "{} can't be assigned from other function",
             self.get_type()
-        ))
+        )))
     }
 
     fn static_assign_literal(
@@ -46,24 +44,28 @@ impl TypeTrait for FunctionType {
         _super: &Type,
         _memory_managers: &mut MemoryManagers,
         _literal: &Literal,
-    ) -> Result<(), String> {
-        Err(format!(
+    ) -> Result<(), CompileError> {
+        Err(CompileError::UnsupportedOperation(format!(
             "{} can't be assigned from literal",
             self.get_type()
-        ))
+        )))
     }
 
     fn get_type(&self) -> TypeSymbol {
         TypeSymbol::Function
     }
 
-    fn get_return_type(&self) -> Result<TypeSymbol, String> {
+    fn get_return_type(&self) -> Result<TypeSymbol, CompileError> {
         match &self.return_type {
-            None => Err("Function does not return a value".to_string()),
+            None => Err(CompileError::NoReturnValue),
             Some(return_type) => Ok(return_type.get_type()),
         }
     }
 
+    fn get_parameter_types(&self) -> Result<Vec<TypeSymbol>, CompileError> {
+        Ok(self.parameters.iter().map(|p| p.get_type()).collect())
+    }
+
     fn get_size(&self) -> usize {
         0
     }
@@ -73,9 +75,12 @@ impl TypeTrait for FunctionType {
         memory_managers: &mut MemoryManagers,
         arguments: Vec<&Type>,
         destination: Option<&Type>,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         if arguments.len() != self.parameters.len() {
-            return Err("Wrong number of arguments".to_string());
+            return Err(CompileError::ArgumentCount {
+                expected: self.parameters.len(),
+                found: arguments.len(),
+            });
         }
 
         // Copy arguments over
@@ -83,31 +88,13 @@ impl TypeTrait for FunctionType {
             self.parameters[index].assign_clone(memory_managers, argument)?;
         }
 
-        // Set jump back address
-        let static_jump_back_address = memory_managers.variable_memory.append(
-            &(memory_managers.program_memory.get_position()
-                + CopyInstruction::get_size()
-                + 2
-                + JumpInstruction::get_size()
-                + 2)
-            .to_le_bytes(),
-        );
-
-        // Copy return value out of function
-        CopyInstruction::new_alloc(
-            memory_managers,
-            static_jump_back_address,
-            self.jump_variable_address,
-            size_of::<usize>(),
-        );
-
-        // Create instruction to jump to function
-        JumpInstruction::new_alloc(memory_managers, self.start_address);
+        // Create instruction to call function
+        CallInstruction::new_alloc(memory_managers, self.start_address);
 
         // Return value
         if destination.is_some() {
             if self.return_type.is_none() {
-                return Err("Function does not return a value".to_string());
+                return Err(CompileError::NoReturnValue);
             }
             destination
                 .unwrap()
@@ -0,0 +1,255 @@
+use crate::errors::{create_op_not_impl_error, CompileError};
+use crate::processing::instructions::add_instruction_13::AddInstruction;
+use crate::processing::instructions::copy_instruction_0::CopyInstruction;
+use crate::processing::instructions::divide_instruction_18::DivideInstruction;
+use crate::processing::instructions::equal_instruction_7::EqualInstruction;
+use crate::processing::instructions::modulo_instruction_19::ModuloInstruction;
+use crate::processing::instructions::multiply_instruction_17::MultiplyInstruction;
+use crate::processing::instructions::not_equal_instruction_14::NotEqualInstruction;
+use crate::processing::instructions::subtract_instruction_16::SubtractInstruction;
+use crate::processing::processor::MemoryManagers;
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::{create_ordering_comparison, get_type, Type, TypeTrait};
+use std::mem::size_of;
+
+pub struct IntegerType {}
+
+impl IntegerType {
+    pub(crate) fn create_empty() -> Self {
+        Self {}
+    }
+}
+
+impl TypeTrait for IntegerType {
+    fn static_assign_literal(
+        &self,
+        _super: &Type,
+        memory_managers: &mut MemoryManagers,
+        literal: &Literal,
+    ) -> Result<(), CompileError> {
+        let value: i64 = match literal {
+            Literal::Int(integer) => *integer,
+            unhandled_literal => {
+                return Err(CompileError::InvalidLiteral(format!(
+                    "{} not supported for {} assignment",
+                    unhandled_literal,
+                    self.get_type()
+                )))
+            }
+        };
+
+        let constant_address = memory_managers.variable_memory.append(&value.to_le_bytes());
+
+        CopyInstruction::new_alloc(
+            memory_managers,
+            constant_address,
+            _super.get_address(),
+            self.get_size(),
+        );
+
+        Ok(())
+    }
+
+    fn create_indexed(
+        &self,
+        _super: &Type,
+        memory_managers: &mut MemoryManagers,
+        argument_literal: &Literal,
+        assignment_literal: &Literal,
+    ) -> Result<usize, CompileError> {
+        let count: usize = match argument_literal {
+            Literal::Int(count) => match (*count).try_into() {
+                Ok(value) => value,
+                Err(_) => {
+                    return Err(CompileError::InvalidLiteral(format!(
+                        "Initialisation argument '{}' out of range",
+                        count
+                    )))
+                }
+            },
+            _ => {
+                return Err(CompileError::InvalidLiteral(format!(
+                    "This type cannot be created with {} initialisation argument",
+                    argument_literal
+                )))
+            }
+        };
+
+        if count == 0 {
+            return Err(CompileError::InvalidLiteral(
+                "Initialisation argument cannot be 0".to_string(),
+            ));
+        }
+
+        let assigner = match assignment_literal {
+            Literal::Int(int) => *int,
+            Literal::None => 0,
+            _ => {
+                return Err(CompileError::InvalidLiteral(format!(
+                    "This type cannot be created with {} assignment argument",
+                    assignment_literal
+                )))
+            }
+        };
+
+        // Assign to all objects in array
+        let mut objs = Vec::with_capacity(count - 1);
+
+        for _ in 1..count {
+            let obj = get_type(&self.get_type(), memory_managers).unwrap();
+            objs.push(obj);
+        }
+
+        self.static_assign_literal(_super, memory_managers, &Literal::Int(assigner))?;
+
+        for i in 1..count {
+            objs[i - 1].static_assign_literal(memory_managers, &Literal::Int(assigner))?;
+        }
+
+        Ok(count)
+    }
+
+    fn get_type(&self) -> TypeSymbol {
+        TypeSymbol::Integer
+    }
+
+    fn get_size(&self) -> usize {
+        size_of::<i64>()
+    }
+
+    fn get_operation_type(
+        &self,
+        _lhs: &Type,
+        operator: &Operator,
+        rhs: Option<&Type>,
+    ) -> Result<TypeSymbol, CompileError> {
+        if rhs.is_none() {
+            return create_op_not_impl_error(operator, self.get_type(), rhs);
+        }
+
+        match rhs.as_ref().unwrap().get_type() {
+            TypeSymbol::Integer => {}
+            _ => return create_op_not_impl_error(operator, self.get_type(), rhs),
+        };
+
+        match operator {
+            Operator::Equal
+            | Operator::NotEqual
+            | Operator::Greater
+            | Operator::Less
+            | Operator::GreaterEqual
+            | Operator::LessEqual => Ok(TypeSymbol::Boolean),
+            Operator::Add
+            | Operator::Subtract
+            | Operator::Product
+            | Operator::Divide
+            | Operator::Modulo => Ok(TypeSymbol::Integer),
+            _ => create_op_not_impl_error(operator, self.get_type(), rhs),
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &Type,
+        memory_managers: &mut MemoryManagers,
+        operator: Operator,
+        rhs: Option<&Type>,
+        destination: &Type,
+    ) -> Result<(), CompileError> {
+        if rhs.is_none() {
+            return create_op_not_impl_error(&operator, self.get_type(), rhs);
+        }
+
+        let rhs = rhs.unwrap();
+
+        match rhs.get_type() {
+            TypeSymbol::Integer => {}
+            _ => return create_op_not_impl_error(&operator, self.get_type(), Some(rhs)),
+        };
+
+        match operator {
+            Operator::Equal => {
+                EqualInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            Operator::NotEqual => {
+                NotEqualInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            // Two's complement addition is identical to unsigned addition
+            Operator::Add => {
+                AddInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            Operator::Subtract => {
+                SubtractInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            Operator::Product => {
+                MultiplyInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            Operator::Divide => {
+                DivideInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                    true,
+                );
+                Ok(())
+            }
+            Operator::Modulo => {
+                ModuloInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                    true,
+                );
+                Ok(())
+            }
+            Operator::Greater | Operator::Less | Operator::GreaterEqual | Operator::LessEqual => {
+                create_ordering_comparison(memory_managers, &operator, lhs, rhs, destination, true);
+                Ok(())
+            }
+            _ => create_op_not_impl_error(&operator, self.get_type(), Some(rhs)),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn TypeTrait> {
+        Box::new(Self::create_empty())
+    }
+}
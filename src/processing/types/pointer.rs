@@ -1,12 +1,16 @@
-use crate::errors::create_op_not_impl_error;
+use crate::errors::{create_op_not_impl_error, CompileError};
 use crate::processing::instructions::add_instruction_13::AddInstruction;
 use crate::processing::instructions::copy_instruction_0::CopyInstruction;
+use crate::processing::instructions::divide_instruction_18::DivideInstruction;
 use crate::processing::instructions::equal_instruction_7::EqualInstruction;
+use crate::processing::instructions::modulo_instruction_19::ModuloInstruction;
+use crate::processing::instructions::multiply_instruction_17::MultiplyInstruction;
 use crate::processing::instructions::not_equal_instruction_14::NotEqualInstruction;
+use crate::processing::instructions::subtract_instruction_16::SubtractInstruction;
 use crate::processing::processor::MemoryManagers;
 use crate::processing::symbols::{Literal, Operator, TypeSymbol};
-use crate::processing::types::{get_type, Type, TypeTrait};
-use std::mem::size_of;
+use crate::processing::types::{create_ordering_comparison, get_type, Type, TypeTrait};
+use crate::util::{encode_usize, POINTER_SIZE};
 
 pub struct PointerType {}
 
@@ -22,31 +26,31 @@ impl TypeTrait for PointerType {
         _super: &Type,
         memory_managers: &mut MemoryManagers,
         literal: &Literal,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         let value: usize;
 
         match literal {
             Literal::Int(integer) => {
                 value = match (*integer).try_into() {
                     Err(_) => {
-                        return Err(format!(
+                        return Err(CompileError::InvalidLiteral(format!(
                             "Cannot fit {}'s value '{}' into Pointer",
                             literal, integer
-                        ))
+                        )))
                     }
                     Ok(value) => value,
                 }
             }
             unhandled_literal => {
-                return Err(format!(
+                return Err(CompileError::InvalidLiteral(format!(
                     "{} not supported for {} assignment",
                     unhandled_literal,
                     self.get_type()
-                ))
+                )))
             }
         }
 
-        let constant_address = memory_managers.variable_memory.append(&value.to_le_bytes());
+        let constant_address = memory_managers.variable_memory.append(&encode_usize(value));
 
         CopyInstruction::new_alloc(
             memory_managers,
@@ -64,32 +68,39 @@ impl TypeTrait for PointerType {
         memory_managers: &mut MemoryManagers,
         argument_literal: &Literal,
         assignment_literal: &Literal,
-    ) -> Result<usize, String> {
+    ) -> Result<usize, CompileError> {
         let count: usize = match argument_literal {
             Literal::Int(count) => match (*count).try_into() {
                 Ok(value) => value,
-                Err(_) => return Err(format!("Initialisation argument '{}' out of range", count)),
+                Err(_) => {
+                    return Err(CompileError::InvalidLiteral(format!(
+                        "Initialisation argument '{}' out of range",
+                        count
+                    )))
+                }
             },
             _ => {
-                return Err(format!(
+                return Err(CompileError::InvalidLiteral(format!(
                     "This type cannot be created with {} initialisation argument",
                     argument_literal
-                ))
+                )))
             }
         };
 
         if count == 0 {
-            return Err("Initialisation argument cannot be 0".to_string());
+            return Err(CompileError::InvalidLiteral(
+                "Initialisation argument cannot be 0".to_string(),
+            ));
         }
 
         let assigner = match assignment_literal {
             Literal::Int(int) => *int,
             Literal::None => 0,
             _ => {
-                return Err(format!(
+                return Err(CompileError::InvalidLiteral(format!(
                     "This type cannot be created with {} assignment argument",
                     assignment_literal
-                ))
+                )))
             }
         };
 
@@ -115,7 +126,7 @@ impl TypeTrait for PointerType {
     }
 
     fn get_size(&self) -> usize {
-        size_of::<usize>()
+        POINTER_SIZE
     }
 
     fn get_operation_type(
@@ -123,7 +134,7 @@ impl TypeTrait for PointerType {
         _lhs: &Type,
         operator: &Operator,
         rhs: Option<&Type>,
-    ) -> Result<TypeSymbol, String> {
+    ) -> Result<TypeSymbol, CompileError> {
         if rhs.is_none() {
             return create_op_not_impl_error(operator, self.get_type(), rhs);
         }
@@ -134,8 +145,17 @@ impl TypeTrait for PointerType {
         };
 
         match operator {
-            Operator::Equal | Operator::NotEqual => Ok(TypeSymbol::Boolean),
-            Operator::Add => Ok(TypeSymbol::Pointer),
+            Operator::Equal
+            | Operator::NotEqual
+            | Operator::Greater
+            | Operator::Less
+            | Operator::GreaterEqual
+            | Operator::LessEqual => Ok(TypeSymbol::Boolean),
+            Operator::Add
+            | Operator::Subtract
+            | Operator::Product
+            | Operator::Divide
+            | Operator::Modulo => Ok(TypeSymbol::Pointer),
             _ => create_op_not_impl_error(operator, self.get_type(), rhs),
         }
     }
@@ -147,7 +167,7 @@ impl TypeTrait for PointerType {
         operator: Operator,
         rhs: Option<&Type>,
         destination: &Type,
-    ) -> Result<(), String> {
+    ) -> Result<(), CompileError> {
         if rhs.is_none() {
             return create_op_not_impl_error(&operator, self.get_type(), rhs);
         }
@@ -190,6 +210,59 @@ impl TypeTrait for PointerType {
                 );
                 Ok(())
             }
+            Operator::Subtract => {
+                SubtractInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            Operator::Product => {
+                MultiplyInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            Operator::Divide => {
+                DivideInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                    false,
+                );
+                Ok(())
+            }
+            Operator::Modulo => {
+                ModuloInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                    false,
+                );
+                Ok(())
+            }
+            Operator::Greater | Operator::Less | Operator::GreaterEqual | Operator::LessEqual => {
+                create_ordering_comparison(
+                    memory_managers,
+                    &operator,
+                    lhs,
+                    rhs,
+                    destination,
+                    false,
+                );
+                Ok(())
+            }
             _ => create_op_not_impl_error(&operator, self.get_type(), Some(rhs)),
         }
     }
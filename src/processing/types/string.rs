@@ -0,0 +1,248 @@
+use crate::errors::{create_op_not_impl_error, CompileError};
+use crate::processing::instructions::concat_instruction_25::ConcatInstruction;
+use crate::processing::instructions::copy_instruction_0::CopyInstruction;
+use crate::processing::instructions::equal_instruction_7::EqualInstruction;
+use crate::processing::instructions::not_equal_instruction_14::NotEqualInstruction;
+use crate::processing::instructions::slice_instruction_27::SliceInstruction;
+use crate::processing::instructions::string_index_instruction_26::StringIndexInstruction;
+use crate::processing::processor::MemoryManagers;
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::{Type, TypeTrait};
+use crate::util::{encode_usize, POINTER_SIZE};
+
+/// Maximum number of characters a string can hold
+pub const STRING_CAPACITY: usize = 256;
+
+/// A string stored as its length (`usize`) followed by `STRING_CAPACITY` bytes. Bytes past the
+/// length are always zero so strings can be compared byte for byte
+pub struct StringType {}
+
+impl StringType {
+    pub(crate) fn create_empty() -> Self {
+        Self {}
+    }
+}
+
+impl TypeTrait for StringType {
+    fn static_assign_literal(
+        &self,
+        _super: &Type,
+        memory_managers: &mut MemoryManagers,
+        literal: &Literal,
+    ) -> Result<(), CompileError> {
+        let value = match literal {
+            Literal::String(string) => string.as_bytes(),
+            Literal::None => &[],
+            unhandled_literal => {
+                return Err(CompileError::InvalidLiteral(format!(
+                    "{} not supported for {} assignment",
+                    unhandled_literal,
+                    self.get_type()
+                )))
+            }
+        };
+
+        if value.len() > STRING_CAPACITY {
+            return Err(CompileError::InvalidLiteral(format!(
+                "String literal of length {} exceeds maximum length of {}",
+                value.len(),
+                STRING_CAPACITY
+            )));
+        }
+
+        let mut constant = Vec::with_capacity(self.get_size());
+        constant.extend(encode_usize(value.len()));
+        constant.extend(value);
+        constant.resize(self.get_size(), 0);
+
+        let constant_address = memory_managers.variable_memory.append(&constant);
+
+        CopyInstruction::new_alloc(
+            memory_managers,
+            constant_address,
+            _super.get_address(),
+            self.get_size(),
+        );
+
+        Ok(())
+    }
+
+    fn get_index(
+        &self,
+        _super: &Type,
+        memory_managers: &mut MemoryManagers,
+        index_pointer: &Type,
+        destination: &Type,
+    ) -> Result<(), CompileError> {
+        if index_pointer.get_type() != TypeSymbol::Pointer {
+            return Err(CompileError::TypeMismatch {
+                expected: TypeSymbol::Pointer,
+                found: index_pointer.get_type(),
+            });
+        }
+
+        if destination.get_type() != TypeSymbol::Character {
+            return Err(CompileError::TypeMismatch {
+                expected: destination.get_type(),
+                found: TypeSymbol::Character,
+            });
+        }
+
+        StringIndexInstruction::new_alloc(
+            memory_managers,
+            _super.get_address(),
+            index_pointer.get_address(),
+            destination.get_address(),
+        );
+
+        Ok(())
+    }
+
+    fn set_index(
+        &self,
+        _super: &Type,
+        _memory_managers: &mut MemoryManagers,
+        _index_pointer: &Type,
+        _source: &Type,
+    ) -> Result<(), CompileError> {
+        Err(CompileError::UnsupportedOperation(format!(
+            "{} cannot be assigned to by index",
+            self.get_type()
+        )))
+    }
+
+    fn is_indexable(&self) -> bool {
+        true
+    }
+
+    fn get_element_type(&self) -> TypeSymbol {
+        TypeSymbol::Character
+    }
+
+    fn get_slice(
+        &self,
+        _super: &Type,
+        memory_managers: &mut MemoryManagers,
+        start: &Type,
+        end: &Type,
+        destination: &Type,
+    ) -> Result<(), CompileError> {
+        if start.get_type() != TypeSymbol::Pointer || end.get_type() != TypeSymbol::Pointer {
+            let found = match start.get_type() {
+                TypeSymbol::Pointer => end.get_type(),
+                found => found,
+            };
+            return Err(CompileError::TypeMismatch {
+                expected: TypeSymbol::Pointer,
+                found,
+            });
+        }
+
+        if destination.get_type() != self.get_type() {
+            return Err(CompileError::TypeMismatch {
+                expected: destination.get_type(),
+                found: self.get_type(),
+            });
+        }
+
+        SliceInstruction::new_alloc(
+            memory_managers,
+            _super.get_address(),
+            start.get_address(),
+            end.get_address(),
+            destination.get_address(),
+            STRING_CAPACITY,
+        );
+
+        Ok(())
+    }
+
+    fn get_type(&self) -> TypeSymbol {
+        TypeSymbol::String
+    }
+
+    fn get_size(&self) -> usize {
+        POINTER_SIZE + STRING_CAPACITY
+    }
+
+    fn get_operation_type(
+        &self,
+        _lhs: &Type,
+        operator: &Operator,
+        rhs: Option<&Type>,
+    ) -> Result<TypeSymbol, CompileError> {
+        if rhs.is_none() {
+            return create_op_not_impl_error(operator, self.get_type(), rhs);
+        }
+
+        match rhs.as_ref().unwrap().get_type() {
+            TypeSymbol::String => {}
+            _ => return create_op_not_impl_error(operator, self.get_type(), rhs),
+        };
+
+        match operator {
+            Operator::Equal | Operator::NotEqual => Ok(TypeSymbol::Boolean),
+            Operator::Add => Ok(TypeSymbol::String),
+            _ => create_op_not_impl_error(operator, self.get_type(), rhs),
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &Type,
+        memory_managers: &mut MemoryManagers,
+        operator: Operator,
+        rhs: Option<&Type>,
+        destination: &Type,
+    ) -> Result<(), CompileError> {
+        if rhs.is_none() {
+            return create_op_not_impl_error(&operator, self.get_type(), rhs);
+        }
+
+        let rhs = rhs.unwrap();
+
+        match rhs.get_type() {
+            TypeSymbol::String => {}
+            _ => return create_op_not_impl_error(&operator, self.get_type(), Some(rhs)),
+        };
+
+        match operator {
+            // Unused bytes are always zero so strings can be compared in full
+            Operator::Equal => {
+                EqualInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            Operator::NotEqual => {
+                NotEqualInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            Operator::Add => {
+                ConcatInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    destination.get_address(),
+                    STRING_CAPACITY,
+                );
+                Ok(())
+            }
+            _ => create_op_not_impl_error(&operator, self.get_type(), Some(rhs)),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn TypeTrait> {
+        Box::new(Self::create_empty())
+    }
+}
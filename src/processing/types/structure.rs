@@ -0,0 +1,213 @@
+use crate::errors::{create_op_not_impl_error, CompileError};
+use crate::processing::instructions::copy_instruction_0::CopyInstruction;
+use crate::processing::instructions::equal_instruction_7::EqualInstruction;
+use crate::processing::instructions::not_equal_instruction_14::NotEqualInstruction;
+use crate::processing::processor::MemoryManagers;
+use crate::processing::reference_manager::ReferenceStack;
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::{create_type_at, get_type_size, Type, TypeTrait};
+
+/// A field of a struct declaration
+pub struct StructField {
+    pub name: String,
+    pub type_symbol: TypeSymbol,
+    /// Number of values if the field is indexed
+    pub count: Option<usize>,
+}
+
+/// A user-defined struct declaration
+pub struct StructDefinition {
+    name: String,
+    fields: Vec<StructField>,
+    size: usize,
+}
+
+impl StructDefinition {
+    pub fn new(
+        name: String,
+        fields: Vec<StructField>,
+        reference_stack: &ReferenceStack,
+    ) -> Result<Self, CompileError> {
+        let mut size = 0;
+        for field in &fields {
+            size += get_type_size(&field.type_symbol, reference_stack)? * field.count.unwrap_or(1);
+        }
+
+        Ok(Self { name, fields, size })
+    }
+
+    /// Gets the name of the struct
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the fields of the struct in memory order
+    pub fn get_fields(&self) -> &[StructField] {
+        &self.fields
+    }
+
+    /// Gets the size of all the struct's fields
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+}
+
+/// An instance of a struct. Fields are stored one after another
+pub struct StructType {
+    id: usize,
+    size: usize,
+    fields: Vec<Type>,
+}
+
+impl StructType {
+    /// Creates a `Type` for the struct `id` at `address` without allocating memory
+    pub(crate) fn create_at(
+        id: usize,
+        address: usize,
+        reference_stack: &ReferenceStack,
+    ) -> Result<Type, CompileError> {
+        let definition = reference_stack.get_struct_definition(id);
+
+        let mut fields = Vec::with_capacity(definition.get_fields().len());
+        let mut offset = 0;
+        for field in definition.get_fields() {
+            let mut created_type =
+                create_type_at(&field.type_symbol, address + offset, reference_stack)?;
+            created_type.indexed_len = field.count;
+            created_type.set_name(field.name.clone());
+            offset += created_type.get_size() * created_type.get_len();
+            fields.push(created_type);
+        }
+
+        Ok(Type {
+            internal_type: Box::new(Self {
+                id,
+                size: definition.get_size(),
+                fields,
+            }),
+            name: None,
+            address,
+            indexed_len: None,
+        })
+    }
+}
+
+impl TypeTrait for StructType {
+    fn static_assign_literal(
+        &self,
+        _super: &Type,
+        memory_managers: &mut MemoryManagers,
+        literal: &Literal,
+    ) -> Result<(), CompileError> {
+        match literal {
+            // Zero every field
+            Literal::None => {}
+            unhandled_literal => {
+                return Err(CompileError::InvalidLiteral(format!(
+                    "{} not supported for {} assignment",
+                    unhandled_literal,
+                    self.get_type()
+                )))
+            }
+        }
+
+        let constant_address = memory_managers
+            .variable_memory
+            .append(&vec![0; self.get_size()]);
+
+        CopyInstruction::new_alloc(
+            memory_managers,
+            constant_address,
+            _super.get_address(),
+            self.get_size(),
+        );
+
+        Ok(())
+    }
+
+    fn get_field(&self, name: &str) -> Result<&Type, CompileError> {
+        match self.fields.iter().find(|f| f.get_name() == name) {
+            Some(field) => Ok(field),
+            None => Err(CompileError::UnknownField {
+                owner: self.get_type().to_string(),
+                field: name.to_string(),
+            }),
+        }
+    }
+
+    fn get_fields(&self) -> Vec<&Type> {
+        self.fields.iter().collect()
+    }
+
+    fn get_type(&self) -> TypeSymbol {
+        TypeSymbol::Struct(self.id)
+    }
+
+    fn get_size(&self) -> usize {
+        self.size
+    }
+
+    fn get_operation_type(
+        &self,
+        _lhs: &Type,
+        operator: &Operator,
+        rhs: Option<&Type>,
+    ) -> Result<TypeSymbol, CompileError> {
+        if rhs.is_none() || rhs.as_ref().unwrap().get_type() != self.get_type() {
+            return create_op_not_impl_error(operator, self.get_type(), rhs);
+        }
+
+        match operator {
+            Operator::Equal | Operator::NotEqual => Ok(TypeSymbol::Boolean),
+            _ => create_op_not_impl_error(operator, self.get_type(), rhs),
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &Type,
+        memory_managers: &mut MemoryManagers,
+        operator: Operator,
+        rhs: Option<&Type>,
+        destination: &Type,
+    ) -> Result<(), CompileError> {
+        if rhs.is_none() || rhs.as_ref().unwrap().get_type() != self.get_type() {
+            return create_op_not_impl_error(&operator, self.get_type(), rhs);
+        }
+
+        let rhs = rhs.unwrap();
+
+        // Unused string bytes are always zero so structs can be compared in full
+        match operator {
+            Operator::Equal => {
+                EqualInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            Operator::NotEqual => {
+                NotEqualInstruction::new_alloc(
+                    memory_managers,
+                    lhs.get_address(),
+                    rhs.get_address(),
+                    self.get_size(),
+                    destination.get_address(),
+                );
+                Ok(())
+            }
+            _ => create_op_not_impl_error(&operator, self.get_type(), Some(rhs)),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn TypeTrait> {
+        Box::new(Self {
+            id: self.id,
+            size: self.size,
+            fields: self.fields.iter().map(|f| f.duplicate()).collect(),
+        })
+    }
+}
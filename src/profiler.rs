@@ -0,0 +1,190 @@
+use crate::col_println;
+use crate::debug_info::DebugInfo;
+use crate::errors::RuntimeError;
+use crate::execution::ExecutionMonitor;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
+use crate::processing::processor::MemoryManagers;
+use crate::translator::{describe_instruction, get_instruction_name};
+use num_format::{Locale, ToFormattedString};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Number of executions of some part of the program and the time spent in them
+#[derive(Clone, Copy, Default, Debug)]
+struct Sample {
+    count: u64,
+    time: Duration,
+}
+
+impl Sample {
+    fn add(&mut self, other: Sample) {
+        self.count += other.count;
+        self.time += other.time;
+    }
+}
+
+/// Counts how often each instruction is executed and how long they take
+#[derive(Default)]
+pub struct Profiler {
+    by_opcode: HashMap<u16, Sample>,
+    by_address: HashMap<usize, Sample>,
+    /// Executions of each call stack as addresses, outermost first. `None` if stacks aren't
+    /// being collected
+    stacks: Option<HashMap<Vec<usize>, u64>>,
+    /// Code and start time of the instruction being executed
+    current: Option<(u16, Instant)>,
+    total: Sample,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a profiler that also records call stacks for `write_folded_stacks`
+    pub fn with_stacks() -> Self {
+        Self {
+            stacks: Some(HashMap::new()),
+            ..Self::default()
+        }
+    }
+
+    /// Prints the executions by instruction, source line and address, most executed first.
+    /// Only the top `limit` lines and addresses are shown
+    pub fn print_report(&self, program: &[u8], debug_info: Option<&DebugInfo>, limit: usize) {
+        col_println!(
+            (cyan, bold),
+            "\nProfile - {} instructions executed [{:?}]",
+            self.total.count.to_formatted_string(&Locale::en),
+            self.total.time
+        );
+
+        println!(
+            "\n{:<24}{:>14}{:>9}{:>14}",
+            "Instruction", "Count", "%", "Time"
+        );
+        for (code, sample) in sorted(&self.by_opcode) {
+            let name = get_instruction_name(*code).unwrap_or("Unknown");
+            self.print_row(&format!("{} ({})", name, code), sample);
+        }
+
+        if let Some(debug_info) = debug_info {
+            let mut by_line: HashMap<usize, Sample> = HashMap::new();
+            for (address, sample) in &self.by_address {
+                if let Some(line) = debug_info.get_line(*address) {
+                    by_line.entry(line.line).or_default().add(*sample);
+                }
+            }
+            let text: HashMap<usize, &str> = debug_info
+                .lines
+                .iter()
+                .map(|l| (l.line, l.text.as_str()))
+                .collect();
+
+            println!("\n{:<24}{:>14}{:>9}{:>14}", "Line", "Count", "%", "Time");
+            for (line, sample) in sorted(&by_line).into_iter().take(limit) {
+                self.print_row(&format!("{}:{}", debug_info.file, line + 1), sample);
+                println!("    | {}", text.get(line).unwrap_or(&""));
+            }
+        }
+
+        println!("\n{:<24}{:>14}{:>9}{:>14}", "Address", "Count", "%", "Time");
+        for (address, sample) in sorted(&self.by_address).into_iter().take(limit) {
+            self.print_row(&format!("[{:0>5}]", address), sample);
+            println!(
+                "    | {}",
+                describe_instruction(program, *address).unwrap_or_default()
+            );
+        }
+    }
+
+    fn print_row(&self, label: &str, sample: &Sample) {
+        println!(
+            "{:<24}{:>14}{:>8.2}%{:>14}",
+            label,
+            sample.count.to_formatted_string(&Locale::en),
+            sample.count as f64 * 100.0 / self.total.count.max(1) as f64,
+            format!("{:.2?}", sample.time)
+        );
+    }
+
+    /// Writes the recorded call stacks in the folded format used by flamegraph tools. Each frame
+    /// is a source line if `debug_info` is given, otherwise a program address
+    pub fn write_folded_stacks(
+        &self,
+        path: impl AsRef<Path>,
+        debug_info: Option<&DebugInfo>,
+    ) -> std::io::Result<()> {
+        let get_frame = |address: usize| match debug_info.and_then(|d| d.get_line(address)) {
+            Some(line) => format!("{}:{}", debug_info.unwrap().file, line.line + 1),
+            None => format!("[{:0>5}]", address),
+        };
+
+        //? Different addresses on the same line are the same frame
+        let mut folded: HashMap<String, u64> = HashMap::new();
+        for (stack, count) in self.stacks.iter().flatten() {
+            let frames: Vec<String> = stack.iter().map(|a| get_frame(*a)).collect();
+            *folded.entry(frames.join(";")).or_default() += count;
+        }
+        let mut folded: Vec<_> = folded.into_iter().collect();
+        folded.sort();
+
+        let mut file = BufWriter::new(File::create(path)?);
+        for (stack, count) in folded {
+            writeln!(file, "{} {}", stack, count)?;
+        }
+        file.flush()
+    }
+}
+
+/// Sorts samples by count, most executed first
+fn sorted<K: Ord>(samples: &HashMap<K, Sample>) -> Vec<(&K, &Sample)> {
+    let mut samples: Vec<_> = samples.iter().collect();
+    samples.sort_by(|(a_key, a), (b_key, b)| b.count.cmp(&a.count).then(a_key.cmp(b_key)));
+    samples
+}
+
+impl ExecutionMonitor for Profiler {
+    fn before_instruction(&mut self, address: usize, memory_managers: &MemoryManagers) {
+        let code = memory_managers
+            .program_memory
+            .memory
+            .get(address..address + INSTRUCTION_CODE_LENGTH)
+            .map(|code| u16::from_le_bytes(code.try_into().unwrap()));
+
+        if let Some(stacks) = &mut self.stacks {
+            //? Call instructions come just before their return addresses
+            let mut stack: Vec<usize> = memory_managers
+                .variable_memory
+                .get_return_addresses()
+                .into_iter()
+                .rev()
+                .map(|a| a.saturating_sub(1))
+                .collect();
+            stack.push(address);
+            *stacks.entry(stack).or_default() += 1;
+        }
+
+        self.current = code.map(|code| (code, Instant::now()));
+    }
+
+    fn after_instruction(
+        &mut self,
+        address: usize,
+        _memory_managers: &MemoryManagers,
+        _result: &Result<(), RuntimeError>,
+    ) {
+        if let Some((code, start)) = self.current.take() {
+            let sample = Sample {
+                count: 1,
+                time: start.elapsed(),
+            };
+            self.by_opcode.entry(code).or_default().add(sample);
+            self.by_address.entry(address).or_default().add(sample);
+            self.total.add(sample);
+        }
+    }
+}
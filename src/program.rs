@@ -0,0 +1,90 @@
+use crate::compiled_file::CompiledFile;
+use crate::debug_info::DebugInfo;
+use crate::errors::LoadError;
+use crate::memory_manager::MemoryManager;
+use crate::optimiser::{optimise, OptimisationReport};
+use crate::processing::processor::MemoryManagers;
+use std::fs;
+use std::path::Path;
+
+/// A compiled program. Can be run by a `Vm` any number of times
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Program {
+    variable_memory: Vec<u8>,
+    program_memory: Vec<u8>,
+    debug_info: Option<DebugInfo>,
+}
+
+impl Program {
+    pub(crate) fn from_memory_managers(
+        memory_managers: MemoryManagers,
+        debug_info: Option<DebugInfo>,
+    ) -> Self {
+        Self {
+            variable_memory: memory_managers.variable_memory.memory,
+            program_memory: memory_managers.program_memory.memory,
+            debug_info,
+        }
+    }
+
+    /// Creates fresh memory to execute the program in
+    pub(crate) fn to_memory_managers(&self) -> MemoryManagers {
+        MemoryManagers {
+            variable_memory: MemoryManager::from_vec(self.variable_memory.clone()),
+            program_memory: MemoryManager::from_vec(self.program_memory.clone()),
+        }
+    }
+
+    /// Gets the debug info mapping program addresses to source lines and variables, if the
+    /// program was compiled with it
+    pub fn get_debug_info(&self) -> Option<&DebugInfo> {
+        self.debug_info.as_ref()
+    }
+
+    /// Gets the compiled instructions
+    pub fn get_bytecode(&self) -> &[u8] {
+        &self.program_memory
+    }
+
+    /// Optimises the program's instructions. See `optimiser::optimise`
+    pub fn optimise(&mut self) -> OptimisationReport {
+        let mut memory_managers = self.to_memory_managers();
+        let report = optimise(&mut memory_managers, self.debug_info.as_mut());
+        self.program_memory = memory_managers.program_memory.memory;
+        report
+    }
+
+    /// Encodes the program in the `.cwhy` format. See `CompiledFile` for the format
+    pub fn encode(&self) -> Vec<u8> {
+        CompiledFile {
+            variable_memory: self.variable_memory.clone(),
+            program_memory: self.program_memory.clone(),
+            debug_info: self.debug_info.as_ref().map(|d| d.encode()),
+        }
+        .encode()
+    }
+
+    /// Decodes a program encoded in the `.cwhy` format
+    pub fn decode(data: &[u8]) -> Result<Self, LoadError> {
+        let file = CompiledFile::decode(data)?;
+        let debug_info = match file.debug_info {
+            Some(data) => Some(DebugInfo::decode(&data)?),
+            None => None,
+        };
+        Ok(Self {
+            variable_memory: file.variable_memory,
+            program_memory: file.program_memory,
+            debug_info,
+        })
+    }
+
+    /// Loads a `.cwhy` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        Self::decode(&fs::read(path)?)
+    }
+
+    /// Saves the program as a `.cwhy` file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), std::io::Error> {
+        fs::write(path, self.encode())
+    }
+}
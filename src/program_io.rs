@@ -0,0 +1,154 @@
+use crate::errors::RuntimeError;
+use std::io::{stdin, stdout, BufRead, Cursor, Write};
+
+/// Where a running program's input comes from and where its output goes
+pub trait ProgramIo {
+    /// Reads a line of input including its line ending. Returns an empty string at the end of
+    /// the input
+    fn read_line(&mut self) -> Result<String, RuntimeError>;
+
+    /// Writes program output
+    fn print(&mut self, text: &str) -> Result<(), RuntimeError>;
+
+    /// Makes sure everything printed so far has been written
+    fn flush(&mut self) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+}
+
+fn to_runtime_error(e: std::io::Error) -> RuntimeError {
+    RuntimeError::Io {
+        message: e.to_string(),
+    }
+}
+
+/// Reads from the process' stdin and writes to its stdout
+#[derive(Clone, Copy, Default, Debug)]
+pub struct StdIo;
+
+impl ProgramIo for StdIo {
+    fn read_line(&mut self) -> Result<String, RuntimeError> {
+        //? Make sure any prompt is visible first
+        self.flush()?;
+        //? Read through the shared handle so nothing else reading stdin, such as the debugger,
+        //? loses buffered input
+        let mut line = String::new();
+        stdin().read_line(&mut line).map_err(to_runtime_error)?;
+        Ok(line)
+    }
+
+    fn print(&mut self, text: &str) -> Result<(), RuntimeError> {
+        stdout()
+            .write_all(text.as_bytes())
+            .map_err(to_runtime_error)
+    }
+
+    fn flush(&mut self) -> Result<(), RuntimeError> {
+        stdout().flush().map_err(to_runtime_error)
+    }
+}
+
+/// Reads from and writes to arbitrary handles, using the process' stdin or stdout for any that
+/// aren't given
+#[derive(Default)]
+pub struct StreamIo {
+    input: Option<Box<dyn BufRead>>,
+    output: Option<Box<dyn Write>>,
+}
+
+impl StreamIo {
+    pub fn new(input: Box<dyn BufRead>, output: Box<dyn Write>) -> Self {
+        Self {
+            input: Some(input),
+            output: Some(output),
+        }
+    }
+
+    pub fn set_input(&mut self, input: Box<dyn BufRead>) {
+        self.input = Some(input);
+    }
+
+    pub fn set_output(&mut self, output: Box<dyn Write>) {
+        self.output = Some(output);
+    }
+}
+
+impl ProgramIo for StreamIo {
+    fn read_line(&mut self) -> Result<String, RuntimeError> {
+        self.flush()?;
+        match &mut self.input {
+            Some(input) => {
+                let mut line = String::new();
+                input.read_line(&mut line).map_err(to_runtime_error)?;
+                Ok(line)
+            }
+            None => StdIo.read_line(),
+        }
+    }
+
+    fn print(&mut self, text: &str) -> Result<(), RuntimeError> {
+        match &mut self.output {
+            Some(output) => output.write_all(text.as_bytes()).map_err(to_runtime_error),
+            None => StdIo.print(text),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), RuntimeError> {
+        match &mut self.output {
+            Some(output) => output.flush().map_err(to_runtime_error),
+            None => StdIo.flush(),
+        }
+    }
+}
+
+/// Reads input from and collects output in memory
+///
+/// # Example
+/// ```
+/// use whython_4::program_io::{BufferIo, ProgramIo};
+///
+/// let mut io = BufferIo::new("first\nsecond\n");
+/// assert_eq!(io.read_line().unwrap(), "first\n");
+/// io.print("Hello").unwrap();
+/// assert_eq!(io.get_output(), "Hello");
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct BufferIo {
+    input: Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+impl BufferIo {
+    /// Creates a `BufferIo` that provides `input` to the program
+    pub fn new(input: &str) -> Self {
+        Self {
+            input: Cursor::new(input.as_bytes().to_vec()),
+            output: Vec::new(),
+        }
+    }
+
+    /// Gets everything printed so far
+    pub fn get_output(&self) -> String {
+        String::from_utf8_lossy(&self.output).to_string()
+    }
+
+    /// Gets and clears everything printed so far
+    pub fn take_output(&mut self) -> String {
+        let output = self.get_output();
+        self.output.clear();
+        output
+    }
+}
+
+impl ProgramIo for BufferIo {
+    fn read_line(&mut self) -> Result<String, RuntimeError> {
+        let mut line = String::new();
+        self.input.read_line(&mut line).map_err(to_runtime_error)?;
+        Ok(line)
+    }
+
+    fn print(&mut self, text: &str) -> Result<(), RuntimeError> {
+        self.output.extend(text.as_bytes());
+        Ok(())
+    }
+}
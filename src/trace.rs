@@ -0,0 +1,154 @@
+use crate::errors::RuntimeError;
+use crate::execution::ExecutionMonitor;
+use crate::memory_manager::MemoryAccess;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
+use crate::processing::processor::MemoryManagers;
+use crate::translator::describe_instruction;
+use std::fs::File;
+use std::io::{stderr, BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+/// Limits which instructions are traced. Every instruction is traced if no limits are set
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TraceFilter {
+    /// Program addresses to trace
+    pub addresses: Option<Range<usize>>,
+    /// Instruction codes to trace
+    pub opcodes: Option<Vec<u16>>,
+}
+
+impl TraceFilter {
+    /// Returns `true` if the instruction should be traced
+    pub fn matches(&self, address: usize, code: u16) -> bool {
+        self.addresses
+            .as_ref()
+            .is_none_or(|addresses| addresses.contains(&address))
+            && self
+                .opcodes
+                .as_ref()
+                .is_none_or(|opcodes| opcodes.contains(&code))
+    }
+
+    /// Parses an address range e.g. '`100..250`'. Either end may be omitted
+    pub fn parse_addresses(text: &str) -> Option<Range<usize>> {
+        let (start, end) = text.split_once("..")?;
+        let start = if start.is_empty() {
+            0
+        } else {
+            start.parse().ok()?
+        };
+        let end = if end.is_empty() {
+            usize::MAX
+        } else {
+            end.parse().ok()?
+        };
+        Some(start..end)
+    }
+
+    /// Parses a comma separated list of instruction codes e.g. '`0,3,12`'
+    pub fn parse_opcodes(text: &str) -> Option<Vec<u16>> {
+        text.split(',')
+            .map(|code| code.trim().parse().ok())
+            .collect()
+    }
+}
+
+/// Logs each executed instruction with the variable memory it read and wrote
+pub struct Tracer {
+    output: Box<dyn Write>,
+    filter: TraceFilter,
+    /// Whether the current instruction is being traced
+    tracing: bool,
+}
+
+impl Tracer {
+    pub fn new(output: Box<dyn Write>, filter: TraceFilter) -> Self {
+        Self {
+            output,
+            filter,
+            tracing: false,
+        }
+    }
+
+    /// Creates a tracer writing to stderr
+    pub fn to_stderr(filter: TraceFilter) -> Self {
+        Self::new(Box::new(stderr()), filter)
+    }
+
+    /// Creates a tracer writing to a new file at `path`
+    pub fn to_file(path: impl AsRef<Path>, filter: TraceFilter) -> std::io::Result<Self> {
+        Ok(Self::new(
+            Box::new(BufWriter::new(File::create(path)?)),
+            filter,
+        ))
+    }
+
+    fn write_trace(
+        &mut self,
+        address: usize,
+        memory_managers: &MemoryManagers,
+        result: &Result<(), RuntimeError>,
+    ) -> std::io::Result<()> {
+        writeln!(
+            self.output,
+            "[{:0>5}] {}",
+            address,
+            describe_instruction(&memory_managers.program_memory.memory, address)
+                .unwrap_or_else(|| "<invalid instruction>".to_string())
+        )?;
+
+        for access in memory_managers.variable_memory.take_access_log() {
+            let (kind, address, data) = match access {
+                MemoryAccess::Read { address, data } => ("read ", address, data),
+                MemoryAccess::Write { address, data } => ("write", address, data),
+            };
+            let bytes: Vec<String> = data.iter().map(|b| format!("{:02X}", b)).collect();
+            writeln!(
+                self.output,
+                "        {} @{}: {}",
+                kind,
+                address,
+                bytes.join(" ")
+            )?;
+        }
+
+        if let Err(e) = result {
+            writeln!(self.output, "        error: {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl ExecutionMonitor for Tracer {
+    fn before_instruction(&mut self, address: usize, memory_managers: &MemoryManagers) {
+        let code = memory_managers
+            .program_memory
+            .memory
+            .get(address..address + INSTRUCTION_CODE_LENGTH)
+            .map(|code| u16::from_le_bytes(code.try_into().unwrap()));
+
+        self.tracing = code.is_some_and(|code| self.filter.matches(address, code));
+        if self.tracing {
+            memory_managers.variable_memory.start_access_log();
+        }
+    }
+
+    fn after_instruction(
+        &mut self,
+        address: usize,
+        memory_managers: &MemoryManagers,
+        result: &Result<(), RuntimeError>,
+    ) {
+        if self.tracing {
+            //? A trace that can't be written shouldn't stop the program
+            self.write_trace(address, memory_managers, result).ok();
+        }
+    }
+}
+
+impl Drop for Tracer {
+    fn drop(&mut self) {
+        self.output.flush().ok();
+    }
+}
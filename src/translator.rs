@@ -1,55 +1,133 @@
+use crate::col_println;
+use crate::debug_info::DebugInfo;
 use crate::processing::instructions::add_instruction_13::AddInstruction;
 use crate::processing::instructions::and_instruction_6::AndInstruction;
+use crate::processing::instructions::call_instruction_22::CallInstruction;
+use crate::processing::instructions::concat_instruction_25::ConcatInstruction;
 use crate::processing::instructions::copy_instruction_0::CopyInstruction;
+use crate::processing::instructions::divide_instruction_18::DivideInstruction;
 use crate::processing::instructions::dynamic_from_copy_instruction_10::DynamicFromCopyInstruction;
 use crate::processing::instructions::dynamic_to_copy_instruction_11::DynamicToCopyInstruction;
 use crate::processing::instructions::equal_instruction_7::EqualInstruction;
+use crate::processing::instructions::greater_equal_instruction_21::GreaterEqualInstruction;
+use crate::processing::instructions::greater_instruction_20::GreaterInstruction;
 use crate::processing::instructions::input_instruction_15::InputInstruction;
 use crate::processing::instructions::invert_instruction_1::InvertInstruction;
+use crate::processing::instructions::jump_if_instruction_12::JumpIfInstruction;
 use crate::processing::instructions::jump_if_not_instruction_2::JumpIfNotInstruction;
 use crate::processing::instructions::jump_instruction_3::JumpInstruction;
 use crate::processing::instructions::jump_variable_instruction_4::JumpVariableInstruction;
+use crate::processing::instructions::modulo_instruction_19::ModuloInstruction;
+use crate::processing::instructions::multiply_instruction_17::MultiplyInstruction;
 use crate::processing::instructions::not_equal_instruction_14::NotEqualInstruction;
 use crate::processing::instructions::or_instruction_8::OrInstruction;
 use crate::processing::instructions::print_chars_instruction_9::PrintCharsInstruction;
 use crate::processing::instructions::print_instruction_5::PrintInstruction;
+use crate::processing::instructions::print_string_instruction_28::PrintStringInstruction;
+use crate::processing::instructions::reserve_frame_instruction_24::ReserveFrameInstruction;
+use crate::processing::instructions::return_instruction_23::ReturnInstruction;
+use crate::processing::instructions::slice_instruction_27::SliceInstruction;
+use crate::processing::instructions::string_index_instruction_26::StringIndexInstruction;
+use crate::processing::instructions::subtract_instruction_16::SubtractInstruction;
+use crate::processing::instructions::INSTRUCTION_CODE_LENGTH;
 
-macro_rules! translate {
-    ($instruction: ident, $data: expr, $i: expr) => {
-        (
-            $instruction::get_debug(&$data[$i..$i + $instruction::get_size()]),
+/// Debug formatter for an instruction's data
+type DebugFormatter = fn(&[u8]) -> String;
+
+macro_rules! info {
+    ($instruction: ident) => {
+        Some((
             $instruction::get_size(),
-        )
+            $instruction::get_debug as DebugFormatter,
+            stringify!($instruction),
+        ))
     };
 }
 
+/// Gets the size of the data following an instruction's code, its debug formatter and the name of
+/// its type
+fn get_instruction_info(code: u16) -> Option<(usize, DebugFormatter, &'static str)> {
+    match code {
+        0 => info!(CopyInstruction),
+        1 => info!(InvertInstruction),
+        2 => info!(JumpIfNotInstruction),
+        3 => info!(JumpInstruction),
+        4 => info!(JumpVariableInstruction),
+        5 => info!(PrintInstruction),
+        6 => info!(AndInstruction),
+        7 => info!(EqualInstruction),
+        8 => info!(OrInstruction),
+        9 => info!(PrintCharsInstruction),
+        10 => info!(DynamicFromCopyInstruction),
+        11 => info!(DynamicToCopyInstruction),
+        12 => info!(JumpIfInstruction),
+        13 => info!(AddInstruction),
+        14 => info!(NotEqualInstruction),
+        15 => info!(InputInstruction),
+        16 => info!(SubtractInstruction),
+        17 => info!(MultiplyInstruction),
+        18 => info!(DivideInstruction),
+        19 => info!(ModuloInstruction),
+        20 => info!(GreaterInstruction),
+        21 => info!(GreaterEqualInstruction),
+        22 => info!(CallInstruction),
+        23 => info!(ReturnInstruction),
+        24 => info!(ReserveFrameInstruction),
+        25 => info!(ConcatInstruction),
+        26 => info!(StringIndexInstruction),
+        27 => info!(SliceInstruction),
+        28 => info!(PrintStringInstruction),
+        _ => None,
+    }
+}
+
+/// Describes the instruction at `address` in the given memory. Returns `None` if there isn't a
+/// complete, valid instruction there
+pub fn describe_instruction(data: &[u8], address: usize) -> Option<String> {
+    let code = data.get(address..address.checked_add(INSTRUCTION_CODE_LENGTH)?)?;
+    let (size, get_debug, _) = get_instruction_info(u16::from_le_bytes(code.try_into().unwrap()))?;
+    let start = address + INSTRUCTION_CODE_LENGTH;
+    Some(get_debug(data.get(start..start.checked_add(size)?)?))
+}
+
+/// Gets the size of the data following the code of the instruction with the given code
+pub fn get_instruction_size(code: u16) -> Option<usize> {
+    let (size, _, _) = get_instruction_info(code)?;
+    Some(size)
+}
+
+/// Gets the name of the instruction with the given code e.g. '`Copy`'
+pub fn get_instruction_name(code: u16) -> Option<&'static str> {
+    let (_, _, name) = get_instruction_info(code)?;
+    Some(name.strip_suffix("Instruction").unwrap_or(name))
+}
+
 /// Prints the instructions and their data in the given memory
 pub fn translate(data: &[u8], translate_one: bool) {
+    translate_with_debug_info(data, translate_one, None)
+}
+
+/// Prints the instructions and their data in the given memory, preceding the instructions of each
+/// source line with that line if `debug_info` is given
+pub fn translate_with_debug_info(data: &[u8], translate_one: bool, debug_info: Option<&DebugInfo>) {
     println!("<------------------------------>");
     let mut i: usize = 0;
     while i < data.len() {
+        if let Some(line) = debug_info.and_then(|d| d.get_line_starting_at(i)) {
+            col_println!(cyan, "{:>5} | {}", line.line + 1, line.text);
+        }
         print!("[{:0>5}] | ", i);
 
-        let code = &data[i..i + 2];
-        i += 2;
-        let (output, size) = match u16::from_le_bytes(code.try_into().unwrap()) {
-            0 => translate!(CopyInstruction, data, i),
-            1 => translate!(InvertInstruction, data, i),
-            2 => translate!(JumpIfNotInstruction, data, i),
-            3 => translate!(JumpInstruction, data, i),
-            4 => translate!(JumpVariableInstruction, data, i),
-            5 => translate!(PrintInstruction, data, i),
-            6 => translate!(AndInstruction, data, i),
-            7 => translate!(EqualInstruction, data, i),
-            8 => translate!(OrInstruction, data, i),
-            9 => translate!(PrintCharsInstruction, data, i),
-            10 => translate!(DynamicFromCopyInstruction, data, i),
-            11 => translate!(DynamicToCopyInstruction, data, i),
-            12 => translate!(JumpInstruction, data, i),
-            13 => translate!(AddInstruction, data, i),
-            14 => translate!(NotEqualInstruction, data, i),
-            15 => translate!(InputInstruction, data, i),
-            code => panic!("Debug not implemented for code {}", code),
+        let size = data
+            .get(i..i + INSTRUCTION_CODE_LENGTH)
+            .and_then(|code| get_instruction_info(u16::from_le_bytes(code.try_into().unwrap())))
+            .map(|(size, _, _)| size);
+        let (size, output) = match (size, describe_instruction(data, i)) {
+            (Some(size), Some(output)) => (size, output),
+            _ => {
+                col_println!(red, "<invalid instruction>");
+                break;
+            }
         };
 
         println!("{}", output);
@@ -58,7 +136,7 @@ pub fn translate(data: &[u8], translate_one: bool) {
             break;
         }
 
-        i += size;
+        i += INSTRUCTION_CODE_LENGTH + size;
     }
     println!("<------------------------------>");
 }
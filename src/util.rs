@@ -1,20 +1,63 @@
+use crate::memory_manager::FRAME_RELATIVE_FLAG;
 use std::io::{stdin, stdout, Read, Write};
-use std::mem::size_of;
+use std::sync::atomic::{AtomicU8, Ordering};
 
 /// Gets a `u8` from `memory` at the pointer
 pub fn get_u8(pointer: &usize, memory: &[u8]) -> u8 {
     u8::from_le_bytes((&memory[*pointer..(*pointer + 1)]).try_into().unwrap())
 }
 
-/// Gets a `usize` from `memory` at the pointer
+/// Size of `ptr` values and instruction operands in compiled programs. Fixed regardless of the
+/// host's pointer width so 32-bit and 64-bit builds can share compiled files
+pub const POINTER_SIZE: usize = 8;
+
+/// Encodes a `usize` as `POINTER_SIZE` little-endian bytes. The top bit is kept as the top bit
+/// of the encoding so flagged addresses survive a change of pointer width
+pub fn encode_usize(value: usize) -> [u8; POINTER_SIZE] {
+    let top = (value >> (usize::BITS - 1)) as u64;
+    let rest = (value & (usize::MAX >> 1)) as u64;
+    ((top << (u64::BITS - 1)) | rest).to_le_bytes()
+}
+
+/// Decodes a `usize` encoded by `encode_usize`. Values too large for this host saturate to the
+/// largest value without the top bit set, which is never a valid address or length
+pub fn decode_usize(bytes: [u8; POINTER_SIZE]) -> usize {
+    let value = u64::from_le_bytes(bytes);
+    let top = (value >> (u64::BITS - 1)) as usize;
+    let rest = usize::try_from(value & (u64::MAX >> 1))
+        .unwrap_or(usize::MAX)
+        .min(usize::MAX >> 1);
+    (top << (usize::BITS - 1)) | rest
+}
+
+/// Gets a `usize` encoded by `encode_usize` from `memory` at the pointer
 pub fn get_usize(pointer: &usize, memory: &[u8]) -> usize {
-    usize::from_le_bytes(
-        (&memory[*pointer..(*pointer + size_of::<usize>())])
+    decode_usize(
+        (&memory[*pointer..(*pointer + POINTER_SIZE)])
             .try_into()
             .unwrap(),
     )
 }
 
+/// Formats a variable address for debug output, showing frame relative addresses as an offset
+/// from the frame pointer
+///
+/// # Example
+/// ```
+/// use whython_4::memory_manager::FRAME_RELATIVE_FLAG;
+/// use whython_4::util::format_address;
+///
+/// assert_eq!(format_address(16), "16");
+/// assert_eq!(format_address(24 | FRAME_RELATIVE_FLAG), "fp+24");
+/// ```
+pub fn format_address(address: usize) -> String {
+    if address & FRAME_RELATIVE_FLAG != 0 {
+        format!("fp+{}", address & !FRAME_RELATIVE_FLAG)
+    } else {
+        address.to_string()
+    }
+}
+
 // /// Evaluates to the `Ok` value or returns `Err(e)`
 // #[macro_export] macro_rules! propagate_error {
 //     ($result: expr) => {
@@ -81,14 +124,61 @@ macro_rules! col_print {
     };
 }
 
+/// Prints formatted text like `col_println!` unless the verbosity is `Verbosity::Quiet`. Used for
+/// progress and timing output
+///
+/// # Example
+/// ```
+/// use whython_4::status_println;
+///
+/// status_println!((green, bold), "Compilation completed [{:?}]", 1);
+/// ```
+#[macro_export]
+macro_rules! status_println {
+    ($($arg:tt)*) => {
+        if $crate::util::get_verbosity() >= $crate::util::Verbosity::Normal {
+            $crate::col_println!($($arg)*)
+        }
+    };
+}
+
+/// How much progress and diagnostic output to print. Program output and errors are always
+/// printed
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Verbosity {
+    /// Only program output and errors
+    Quiet,
+    /// Progress and timing
+    Normal,
+    /// Progress, timing and `[INFO]` messages
+    Verbose,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Sets the verbosity for the whole process
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn get_verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
 /// Prints a warning
 pub fn warn(warning: &str) {
     col_println!((yellow, bold), "[WARNING]: {}", warning);
 }
 
-/// Prints information
+/// Prints information if the verbosity is `Verbosity::Verbose`
 pub fn info(info: &str) {
-    col_println!((blue, bold), "[INFO]: {}", info);
+    if get_verbosity() >= Verbosity::Verbose {
+        col_println!((blue, bold), "[INFO]: {}", info);
+    }
 }
 
 /// Waits for enter key to be pressed
@@ -0,0 +1,172 @@
+use crate::errors::ExecutionError;
+use crate::execution::{execute_with, ExecutionLimits};
+use crate::memory_manager::{DEFAULT_MAX_MEMORY, DEFAULT_MAX_STACK_DEPTH};
+use crate::program::Program;
+use crate::program_io::{ProgramIo, StreamIo};
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Stops a running `Vm`, e.g. from another thread. Clones cancel the same `Vm`
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops any program running with this token with `RuntimeError::Interrupted`
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Allows programs to run with this token again after being cancelled
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Runs compiled `Program`s
+///
+/// # Example
+/// ```
+/// use whython_4::{Compiler, Vm};
+///
+/// let program = Compiler::new().compile("example.why", "print 5\n").unwrap();
+/// let mut vm = Vm::new().with_instruction_limit(1_000);
+/// vm.run(&program).unwrap();
+/// ```
+pub struct Vm {
+    streams: StreamIo,
+    /// Used instead of `streams` if set
+    io: Option<Box<dyn ProgramIo>>,
+    cancellation_token: CancellationToken,
+    limits: ExecutionLimits,
+    max_stack_depth: usize,
+    max_memory: usize,
+}
+
+impl Default for Vm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Vm {
+    /// Creates a `Vm` using the process' stdin and stdout, without any limits
+    pub fn new() -> Self {
+        Self {
+            streams: StreamIo::default(),
+            io: None,
+            cancellation_token: CancellationToken::new(),
+            limits: ExecutionLimits::default(),
+            max_stack_depth: DEFAULT_MAX_STACK_DEPTH,
+            max_memory: DEFAULT_MAX_MEMORY,
+        }
+    }
+
+    /// Reads program input from `stdin` instead of the process' stdin
+    pub fn with_stdin(mut self, stdin: impl BufRead + 'static) -> Self {
+        self.streams.set_input(Box::new(stdin));
+        self
+    }
+
+    /// Writes program output to `stdout` instead of the process' stdout
+    pub fn with_stdout(mut self, stdout: impl Write + 'static) -> Self {
+        self.streams.set_output(Box::new(stdout));
+        self
+    }
+
+    /// Routes all program input and output through `io`, ignoring `with_stdin` and `with_stdout`
+    pub fn with_io(mut self, io: impl ProgramIo + 'static) -> Self {
+        self.io = Some(Box::new(io));
+        self
+    }
+
+    /// Uses `token` to cancel programs instead of the `Vm`'s own token
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = token;
+        self
+    }
+
+    /// Stops programs after executing `limit` instructions
+    pub fn with_instruction_limit(mut self, limit: u64) -> Self {
+        self.limits.max_instructions = Some(limit);
+        self
+    }
+
+    /// Stops programs after running for `limit`
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.limits.max_time = Some(limit);
+        self
+    }
+
+    /// Sets the maximum number of nested function calls
+    pub fn with_max_stack_depth(mut self, max_stack_depth: usize) -> Self {
+        self.max_stack_depth = max_stack_depth;
+        self
+    }
+
+    /// Sets the maximum size of variable memory in bytes
+    pub fn with_max_memory(mut self, max_memory: usize) -> Self {
+        self.max_memory = max_memory;
+        self
+    }
+
+    /// Gets a token that cancels programs run by this `Vm`
+    pub fn get_cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
+
+    /// Runs `program` in fresh memory until it finishes, fails, exceeds a limit or is cancelled
+    pub fn run(&mut self, program: &Program) -> Result<(), ExecutionError> {
+        //? Taken while running so the rest of the `Vm` can be borrowed
+        let mut streams = std::mem::take(&mut self.streams);
+        let mut io = self.io.take();
+        let result = match &mut io {
+            Some(io) => self.run_with_io(program, io.as_mut()),
+            None => self.run_with_io(program, &mut streams),
+        };
+        self.streams = streams;
+        self.io = io;
+        result
+    }
+
+    /// Runs `program` like `run`, but with `io` instead of the `Vm`'s own input and output
+    ///
+    /// # Example
+    /// ```
+    /// use whython_4::program_io::BufferIo;
+    /// use whython_4::{Compiler, Vm};
+    ///
+    /// let program = Compiler::new().compile("example.why", "print 5\n").unwrap();
+    /// let mut io = BufferIo::new("");
+    /// Vm::new().run_with_io(&program, &mut io).unwrap();
+    /// assert_eq!(io.get_output(), "5\n");
+    /// ```
+    pub fn run_with_io(
+        &self,
+        program: &Program,
+        io: &mut dyn ProgramIo,
+    ) -> Result<(), ExecutionError> {
+        let mut memory_managers = program.to_memory_managers();
+        memory_managers.variable_memory.max_stack_depth = self.max_stack_depth;
+        memory_managers.variable_memory.max_memory = self.max_memory;
+
+        execute_with(
+            &mut memory_managers,
+            &self.cancellation_token.cancelled,
+            io,
+            self.limits,
+            None,
+        )
+    }
+}
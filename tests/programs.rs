@@ -0,0 +1,151 @@
+//! Golden-file tests for Whython programs
+//!
+//! Every `tests/programs/<name>.why` is compiled and run, reading input from `<name>.in` if it
+//! exists. Its output, followed by any compile or runtime errors, must match
+//! `<name>.expected`, and must still match it, apart from program addresses, once optimised.
+//! Run with `BLESS=1` to write the current results to the `.expected` files
+//! instead, e.g. after adding a program or intentionally changing output
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use whython_4::program_io::BufferIo;
+    use whython_4::{Compiler, Vm};
+
+    /// Stops programs that never finish instead of hanging the test
+    const INSTRUCTION_LIMIT: u64 = 1_000_000;
+
+    /// Compiles, optionally optimises and runs `source`, returning its output followed by any
+    /// errors
+    fn run_program(file_name: &str, source: &str, input: &str, optimise: bool) -> String {
+        let mut program = match Compiler::new().compile(file_name, source) {
+            Ok(program) => program,
+            Err(diagnostics) => {
+                return diagnostics
+                    .iter()
+                    .map(|d| format!("[compile error] {}\n", d))
+                    .collect();
+            }
+        };
+        if optimise {
+            program.optimise();
+        }
+
+        let mut io = BufferIo::new(input);
+        let result = Vm::new()
+            .with_instruction_limit(INSTRUCTION_LIMIT)
+            .run_with_io(&program, &mut io);
+        let mut output = io.take_output();
+
+        if let Err(e) = result {
+            if !output.is_empty() && !output.ends_with('\n') {
+                output.push('\n');
+            }
+            let line = program
+                .get_debug_info()
+                .and_then(|debug_info| debug_info.get_line(e.address));
+            match line {
+                Some(line) => output += &format!("[runtime error] Line {}: {}\n", line.line + 1, e),
+                None => output += &format!("[runtime error] {}\n", e),
+            }
+        }
+
+        output
+    }
+
+    /// Removes the program addresses from error messages as they change when optimising
+    fn strip_addresses(output: &str) -> String {
+        const PREFIX: &str = " at program address [";
+
+        let mut output = output.to_string();
+        while let Some(start) = output.find(PREFIX) {
+            match output[start..].find(']') {
+                Some(end) => output.replace_range(start..start + end + 1, ""),
+                None => break,
+            }
+        }
+        output
+    }
+
+    /// Gets every `.why` file in `tests/programs`, sorted by name
+    fn get_programs() -> Vec<PathBuf> {
+        let mut directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        directory.push("tests/programs");
+
+        let mut programs: Vec<PathBuf> = fs::read_dir(&directory)
+            .unwrap_or_else(|e| panic!("Failed to read '{}' - {}", directory.display(), e))
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|e| e == "why"))
+            .collect();
+        programs.sort();
+        programs
+    }
+
+    /// Runs the program at `path`, returning a description of how its result differs from the
+    /// expected result, if it does
+    fn check_program(path: &Path, bless: bool) -> Option<String> {
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        let source = fs::read_to_string(path).unwrap();
+        //? Missing input is the same as empty input
+        let input = fs::read_to_string(path.with_extension("in")).unwrap_or_default();
+
+        let actual = run_program(&file_name, &source, &input, false);
+
+        let expected_path = path.with_extension("expected");
+        if bless {
+            fs::write(&expected_path, &actual).unwrap();
+            return None;
+        }
+
+        let expected = match fs::read_to_string(&expected_path) {
+            Ok(expected) => expected,
+            Err(_) => {
+                return Some(format!(
+                    "{}: '{}' not found. Run with BLESS=1 to create it\n--- actual ---\n{}",
+                    file_name,
+                    expected_path.display(),
+                    actual
+                ))
+            }
+        };
+        if expected != actual {
+            return Some(format!(
+                "{}: output differs\n--- expected ---\n{}\n--- actual ---\n{}",
+                file_name, expected, actual
+            ));
+        }
+
+        let optimised = run_program(&file_name, &source, &input, true);
+        if strip_addresses(&expected) != strip_addresses(&optimised) {
+            return Some(format!(
+                "{}: optimised output differs\n--- expected ---\n{}\n--- actual ---\n{}",
+                file_name, expected, optimised
+            ));
+        }
+        None
+    }
+
+    #[test]
+    fn test_programs() {
+        let bless = std::env::var("BLESS").is_ok_and(|v| v != "0");
+
+        let programs = get_programs();
+        assert!(!programs.is_empty(), "No programs found");
+
+        let failures: Vec<String> = programs
+            .iter()
+            .filter_map(|path| check_program(path, bless))
+            .collect();
+
+        println!("Checked {} programs", programs.len());
+        if !failures.is_empty() {
+            panic!(
+                "{} of {} programs failed:\n\n{}",
+                failures.len(),
+                programs.len(),
+                failures.join("\n")
+            );
+        }
+    }
+}
@@ -4,10 +4,16 @@ mod tests {
     use std::path::PathBuf;
     use std::sync::atomic::AtomicBool;
     use walkdir::WalkDir;
-    use whython_4::execution::execute;
+    use whython_4::compiled_file::{CompiledFile, FORMAT_VERSION};
+    use whython_4::errors::LoadError;
+    use whython_4::execution::{execute_with, ExecutionLimits};
     use whython_4::memory_manager::MemoryManager;
+    use whython_4::optimiser::OptimisationReport;
     use whython_4::processing::processor::MemoryManagers;
+    use whython_4::program_io::BufferIo;
     use whython_4::translator::translate;
+    use whython_4::util::POINTER_SIZE;
+    use whython_4::{Compiler, Vm};
 
     #[test]
     fn test_instruction_implementation() {
@@ -42,24 +48,125 @@ mod tests {
         let atomic_bool = AtomicBool::from(true);
 
         for i in 0..count {
-            // ! Skip input instruction test
-            if i == 15 {
-                continue;
-            }
-
             let encoded_code = i.to_le_bytes();
             for (j, b) in encoded_code.iter().enumerate() {
                 dummy_data[j] = *b;
             }
 
-            execute(
+            execute_with(
                 &mut MemoryManagers {
                     variable_memory: MemoryManager::from_vec(Vec::from(dummy_data)),
                     program_memory: MemoryManager::from_vec(Vec::from(dummy_data)),
                 },
                 &atomic_bool,
+                &mut BufferIo::new("input\n"),
+                ExecutionLimits::default(),
+                None,
             )
             .ignore();
         }
     }
+
+    #[test]
+    fn test_program_io() {
+        let source = "printc \"Pick: \"\nchar c = \"-\"\ninput c\nprintc c\nprint 5\n";
+        let program = Compiler::new().compile("test.why", source).unwrap();
+
+        let mut io = BufferIo::new("x\n");
+        Vm::new().run_with_io(&program, &mut io).unwrap();
+        assert_eq!(io.get_output(), "Pick: x5\n");
+    }
+
+    #[test]
+    fn test_compiled_file_rejection() {
+        let program = Compiler::new()
+            .compile("test.why", "int a = 1\nprint a\n")
+            .unwrap();
+        let encoded = program.encode();
+        assert!(CompiledFile::decode(&encoded).is_ok());
+
+        let corrupt = |corrupt: &dyn Fn(&mut Vec<u8>)| {
+            let mut data = encoded.clone();
+            corrupt(&mut data);
+            CompiledFile::decode(&data).unwrap_err()
+        };
+
+        assert!(matches!(
+            corrupt(&|data| data[0] = b'X'),
+            LoadError::NotCompiledFile
+        ));
+        assert!(matches!(
+            corrupt(&|data| data[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes())),
+            LoadError::UnsupportedVersion { version, supported }
+                if version == FORMAT_VERSION + 1 && supported == FORMAT_VERSION
+        ));
+        assert!(matches!(
+            corrupt(&|data| data[6] = (POINTER_SIZE * 2) as u8),
+            LoadError::PointerWidthMismatch { found, expected }
+                if found == POINTER_SIZE * 2 && expected == POINTER_SIZE
+        ));
+        assert!(matches!(
+            corrupt(&|data| data[7] = 1),
+            LoadError::UnsupportedEndianness
+        ));
+        //? Cut off part way through the first section table entry after the 16 byte header
+        assert!(matches!(
+            corrupt(&|data| data.truncate(20)),
+            LoadError::Truncated
+        ));
+        assert!(matches!(
+            corrupt(&|data| *data.last_mut().unwrap() ^= 1),
+            LoadError::ChecksumMismatch
+        ));
+    }
+
+    #[test]
+    fn test_optimisation_report() {
+        //? 'c' is never read, '!b' is only used by the 'if' and reads of 'a' and 'b' can read the
+        //? constants they were copied from instead
+        let source = "bool b = false\nint a = 1\nint c = a\nif !b\n    print a\n";
+        let mut program = Compiler::new().compile("test.why", source).unwrap();
+
+        let report = program.optimise();
+        assert_eq!(
+            report,
+            OptimisationReport {
+                original_size: 174,
+                optimised_size: 52,
+                copies_removed: 4,
+                jumps_threaded: 0,
+                jumps_removed: 0,
+                inversions_folded: 1,
+                reads_forwarded: 4,
+            }
+        );
+        assert_eq!(report.get_size_reduction(), 122);
+        assert_eq!(program.get_bytecode().len(), 52);
+
+        let mut io = BufferIo::new("");
+        Vm::new().run_with_io(&program, &mut io).unwrap();
+        assert_eq!(io.get_output(), "1\n");
+    }
+
+    #[test]
+    fn test_constant_forwarding() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/programs/integer_arithmetic.why");
+        let source = std::fs::read_to_string(&path).unwrap();
+        let expected = std::fs::read_to_string(path.with_extension("expected")).unwrap();
+        let mut program = Compiler::new()
+            .compile("integer_arithmetic.why", &source)
+            .unwrap();
+
+        //? Most values are copied from a constant and only read before they're next written
+        let report = program.optimise();
+        assert_eq!(report.original_size, 1220);
+        assert_eq!(report.get_size_reduction(), 286);
+        assert_eq!(report.copies_removed, 11);
+        assert_eq!(report.reads_forwarded, 19);
+
+        let mut io = BufferIo::new("");
+        Vm::new().run_with_io(&program, &mut io).unwrap();
+        assert_eq!(io.get_output(), expected);
+    }
 }